@@ -16,16 +16,19 @@ where
     let mut crate_path = TokenStream2::new();
 
     quote!(crate_path, { ::databuf });
-    f(Expand::new(crate_path, &input, &mut output));
+    match Expand::new(crate_path, &input, &mut output) {
+        Ok(expand) => f(expand),
+        Err(err) => return err.to_compile_error().into(),
+    }
     TokenStream::from(output)
 }
 
-#[proc_macro_derive(Encode)]
+#[proc_macro_derive(Encode, attributes(databuf))]
 pub fn encode(input: TokenStream) -> TokenStream {
     expand(input, |mut expend| expend.encoder())
 }
 
-#[proc_macro_derive(Decode)]
+#[proc_macro_derive(Decode, attributes(databuf))]
 pub fn decode(input: TokenStream) -> TokenStream {
     expand(input, |mut expend| expend.decoder())
 }
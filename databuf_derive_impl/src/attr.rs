@@ -0,0 +1,312 @@
+use super::*;
+
+/// Options of the `config` module, that can be pinned per field.
+const OPTIONS: [&str; 5] = ["num", "len", "int_codec", "char_codec", "float"];
+
+/// `#[databuf(...)]` attributes of a type.
+#[derive(Default)]
+pub struct TypeAttrs {
+    /// `#[databuf(config = ...)]` pins the config of the whole type.
+    pub config: Option<Expr>,
+    /// `#[databuf(bit_order = LsbFirst)]` sets the order of bit fields.
+    pub bit_order: Option<Ident>,
+    /// `#[databuf(discriminant = U8)]` sets the encoding of enum discriminants.
+    pub discriminant: Option<Ident>,
+    /// `#[databuf(crate = "path::to::databuf")]` sets the path of `databuf` crate.
+    pub crate_path: Option<Path>,
+    /// `#[databuf(bound = "T: Trait")]` replaces the inferred bounds of the impls.
+    pub bound: Bounds,
+    /// `#[databuf(transparent)]` encodes a struct exactly like its single field.
+    pub transparent: Option<Ident>,
+}
+
+impl TypeAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut this = Self::default();
+        let mut seen = Vec::new();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("databuf")) {
+            attr.parse_nested_meta(|meta| {
+                check_duplicate(&meta, &mut seen)?;
+                if meta.path.is_ident("config") {
+                    this.config = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("bit_order") {
+                    let order: Ident = meta.value()?.parse()?;
+                    if order != "MsbFirst" && order != "LsbFirst" {
+                        return Err(Error::new(
+                            order.span(),
+                            "expected `MsbFirst` or `LsbFirst`",
+                        ));
+                    }
+                    this.bit_order = Some(order);
+                    Ok(())
+                } else if meta.path.is_ident("discriminant") {
+                    let encoding: Ident = meta.value()?.parse()?;
+                    if !["U8", "BEU15", "LEB128"].iter().any(|e| encoding == e) {
+                        return Err(Error::new(
+                            encoding.span(),
+                            "expected `U8`, `BEU15` or `LEB128`",
+                        ));
+                    }
+                    this.discriminant = Some(encoding);
+                    Ok(())
+                } else if meta.path.is_ident("crate") {
+                    let lit: LitStr = meta.value()?.parse()?;
+                    this.crate_path = Some(lit.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("bound") {
+                    this.bound.parse(&meta)
+                } else if meta.path.is_ident("transparent") {
+                    this.transparent = meta.path.get_ident().cloned();
+                    Ok(())
+                } else {
+                    Err(meta.error(
+                        "unknown attribute, expected `config`, `bit_order`, `discriminant`, `crate`, `bound` or `transparent`",
+                    ))
+                }
+            })?;
+        }
+        Ok(this)
+    }
+}
+
+/// `#[databuf(...)]` attributes of a field.
+#[derive(Default)]
+pub struct FieldAttrs {
+    /// Pinned options, e.g. `#[databuf(num = BE)]` is `(num, BE)`.
+    pub options: Vec<(Ident, Ident)>,
+    /// `#[databuf(bits = 4)]` packs the field in 4 bits.
+    pub bits: Option<u32>,
+    /// `#[databuf(skip)]` omits the field, It is `Default::default()` on decode.
+    pub skip: bool,
+    /// `#[databuf(default = "path::to_fn")]` omits the field, It is `path::to_fn()` on decode.
+    pub default: Option<Path>,
+    /// `#[databuf(with = "module")]` encodes the field with `module::encode` and `module::decode`.
+    pub with: Option<Path>,
+    /// `#[databuf(with = "module", from_reader)]` also decodes the field from reader with `module::decode_from_reader`.
+    pub from_reader: bool,
+    /// `#[databuf(encode_with = "path::to_fn")]` encodes the field with `path::to_fn`.
+    pub encode_with: Option<Path>,
+    /// `#[databuf(decode_with = "path::to_fn")]` decodes the field with `path::to_fn`.
+    pub decode_with: Option<Path>,
+    /// `#[databuf(bound = "T: Trait")]` replaces the inferred bounds of the field.
+    pub bound: Bounds,
+}
+
+impl FieldAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut this = Self::default();
+        let mut seen = Vec::new();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("databuf")) {
+            attr.parse_nested_meta(|meta| {
+                check_duplicate(&meta, &mut seen)?;
+                if meta.path.is_ident("bits") {
+                    let lit: LitInt = meta.value()?.parse()?;
+                    let bits = lit.base10_parse()?;
+                    if !(1..=64).contains(&bits) {
+                        return Err(Error::new(lit.span(), "bits must be in `1..=64`"));
+                    }
+                    this.bits = Some(bits);
+                    return Ok(());
+                }
+                if meta.path.is_ident("skip") {
+                    this.skip = true;
+                    return Ok(());
+                }
+                if meta.path.is_ident("from_reader") {
+                    this.from_reader = true;
+                    return Ok(());
+                }
+                if meta.path.is_ident("bound") {
+                    return this.bound.parse(&meta);
+                }
+                for (name, path) in [
+                    ("default", &mut this.default),
+                    ("with", &mut this.with),
+                    ("encode_with", &mut this.encode_with),
+                    ("decode_with", &mut this.decode_with),
+                ] {
+                    if meta.path.is_ident(name) {
+                        let lit: LitStr = meta.value()?.parse()?;
+                        *path = Some(lit.parse()?);
+                        return Ok(());
+                    }
+                }
+                let Some(opt) = meta
+                    .path
+                    .get_ident()
+                    .filter(|i| OPTIONS.iter().any(|o| i == o))
+                else {
+                    return Err(meta.error(
+                        "unknown attribute, expected `num`, `len`, `int_codec`, `char_codec`, `float`, `bits`, `skip`, `default`, `with`, `from_reader`, `encode_with`, `decode_with` or `bound`",
+                    ));
+                };
+                let name: Ident = meta.value()?.parse()?;
+                if config::find(opt, &name).is_none() {
+                    return Err(Error::new(
+                        name.span(),
+                        format!("unknown `{opt}` option, expected {}", config::names_of(opt)),
+                    ));
+                }
+                this.options.push((opt.clone(), name));
+                Ok(())
+            })?;
+            if this.bits.is_some() && !this.options.is_empty() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "bit fields don't use `num`, `len`, `int_codec`, `char_codec` or `float`",
+                ));
+            }
+            if this.from_reader && this.with.is_none() {
+                return Err(Error::new_spanned(
+                    attr,
+                    "`from_reader` decodes with `module::decode_from_reader`, It needs `with = \"module\"`",
+                ));
+            }
+            let has_with =
+                this.with.is_some() || this.encode_with.is_some() || this.decode_with.is_some();
+            if this.is_skipped() && (this.bits.is_some() || !this.options.is_empty() || has_with) {
+                return Err(Error::new_spanned(
+                    attr,
+                    "skipped fields are not encoded, They don't use other attributes",
+                ));
+            }
+            if this.with.is_some() && (this.encode_with.is_some() || this.decode_with.is_some()) {
+                return Err(Error::new_spanned(
+                    attr,
+                    "`with` can't be used with `encode_with` or `decode_with`",
+                ));
+            }
+            if this.bits.is_some() && has_with {
+                return Err(Error::new_spanned(
+                    attr,
+                    "bit fields don't use `with`, `encode_with` or `decode_with`",
+                ));
+            }
+        }
+        Ok(this)
+    }
+
+    /// Whether the field is omitted with `#[databuf(skip)]` or `#[databuf(default = ...)]`.
+    pub fn is_skipped(&self) -> bool {
+        self.skip || self.default.is_some()
+    }
+
+    /// Value of the skipped field on decode.
+    pub fn default_value(&self, o: &mut TokenStream) {
+        match &self.default {
+            Some(path) => {
+                quote!(o, { #path() });
+            }
+            None => {
+                quote!(o, { ::core::default::Default::default() });
+            }
+        }
+    }
+
+    /// Path of the function that encodes the field, Set with `with` or `encode_with`.
+    pub fn encoder(&self) -> Option<Path> {
+        self.encode_with.clone().or_else(|| {
+            let mut path = self.with.clone()?;
+            path.segments.push(parse_quote!(encode));
+            Some(path)
+        })
+    }
+
+    /// Path of the function that decodes the field, Set with `with` or `decode_with`.
+    pub fn decoder(&self) -> Option<Path> {
+        self.decode_with.clone().or_else(|| {
+            let mut path = self.with.clone()?;
+            path.segments.push(parse_quote!(decode));
+            Some(path)
+        })
+    }
+
+    /// Path of the function that decodes the field from reader, Set with `with` and `from_reader`.
+    pub fn reader_decoder(&self) -> Option<Path> {
+        let mut path = self.with.clone().filter(|_| self.from_reader)?;
+        path.segments.push(parse_quote!(decode_from_reader));
+        Some(path)
+    }
+
+    /// Options pinned by this field of type `ty`, `None` if it uses the config of the caller.
+    pub fn pinned(&self, ty: &Type) -> Option<Pinned> {
+        (!self.options.is_empty()).then(|| Pinned::new(&self.options, ty))
+    }
+}
+
+/// Where predicates of `#[databuf(bound = "...")]`.
+pub type Predicates = punctuated::Punctuated<WherePredicate, Token![,]>;
+
+/// Impls generated by the derive macros, that have their own bounds.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Impl {
+    Encode,
+    Decode,
+    DecodeFromReader,
+}
+
+/// `#[databuf(bound = "...")]` sets the bounds of every impl,
+/// `#[databuf(bound(encode = "...", decode = "...", decode_from_reader = "..."))]` sets them separately.
+#[derive(Default)]
+pub struct Bounds {
+    pub encode: Option<Predicates>,
+    pub decode: Option<Predicates>,
+    pub decode_from_reader: Option<Predicates>,
+}
+
+impl Bounds {
+    fn parse(&mut self, meta: &meta::ParseNestedMeta) -> Result<()> {
+        let parse = |meta: &meta::ParseNestedMeta| -> Result<Predicates> {
+            let lit: LitStr = meta.value()?.parse()?;
+            lit.parse_with(Predicates::parse_terminated)
+        };
+        if meta.input.peek(Token![=]) {
+            let predicates = parse(meta)?;
+            self.encode = Some(predicates.clone());
+            self.decode = Some(predicates.clone());
+            self.decode_from_reader = Some(predicates);
+            return Ok(());
+        }
+        meta.parse_nested_meta(|meta| {
+            if meta.path.is_ident("encode") {
+                self.encode = Some(parse(&meta)?);
+                Ok(())
+            } else if meta.path.is_ident("decode") {
+                self.decode = Some(parse(&meta)?);
+                Ok(())
+            } else if meta.path.is_ident("decode_from_reader") {
+                self.decode_from_reader = Some(parse(&meta)?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `encode`, `decode` or `decode_from_reader`"))
+            }
+        })
+    }
+
+    /// Bounds of the impl.
+    pub fn get(&self, of: Impl) -> Option<&Predicates> {
+        match of {
+            Impl::Encode => self.encode.as_ref(),
+            Impl::Decode => self.decode.as_ref(),
+            Impl::DecodeFromReader => self.decode_from_reader.as_ref(),
+        }
+    }
+}
+
+/// Rejects an attribute that is already set, `bound` can be split across attributes.
+fn check_duplicate(meta: &meta::ParseNestedMeta, seen: &mut Vec<Ident>) -> Result<()> {
+    let Some(name) = meta.path.get_ident().filter(|name| *name != "bound") else {
+        return Ok(());
+    };
+    if seen.contains(name) {
+        return Err(meta.error(format!("duplicate `{name}` attribute")));
+    }
+    seen.push(name.clone());
+    Ok(())
+}
+
+/// Parse `#[databuf(...)]` attributes, They are validated by [Expand::new], So it doesn't fail here.
+pub fn parse<T>(attrs: &[Attribute], parse: fn(&[Attribute]) -> Result<T>) -> T {
+    parse(attrs).unwrap_or_else(|err| panic!("{err}"))
+}
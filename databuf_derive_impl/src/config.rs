@@ -0,0 +1,370 @@
+use super::*;
+
+/// An option of `databuf::config`, Its bits are the constants of that module.
+struct Opt {
+    /// Name of the field attribute, e.g. `num`.
+    attr: &'static str,
+    /// Module of `databuf::config` that defines the bits, e.g. `float_order`.
+    module: &'static str,
+    /// Constants of the module, Every value of the option in the config of the caller.
+    values: &'static [&'static str],
+    /// Values that can be pinned, Constants of `databuf::config::#attr` module.
+    names: &'static [&'static str],
+    /// Whether the option changes the encoded size, Byte order and NaN encoding don't.
+    sizes: bool,
+}
+
+const NUM: &[&str] = &[
+    "LE",
+    "BE",
+    "NE",
+    "LEB128",
+    "BEU62",
+    "VLQ",
+    "COMPACT_SIZE",
+    "SQLITE_VARINT",
+    "PREFIX_VARINT",
+];
+const LEN: &[&str] = &[
+    "BEU30",
+    "BEU29",
+    "BEU22",
+    "BEU15",
+    "BEU62",
+    "VLQ",
+    "COMPACT_SIZE",
+    "SQLITE_VARINT",
+    "PREFIX_VARINT",
+    "U8",
+    "U16",
+    "U32",
+    "U64",
+    "LEB128",
+];
+const INT_CODEC: &[&str] = &["ZIG_ZAG", "SIGN_BIT", "TWOS_COMPLEMENT"];
+const CHAR_CODEC: &[&str] = &["UTF32", "UTF8"];
+
+/// Options of a field, that are pinned or taken from the config of the caller.
+///
+/// `float` option is split into byte order, format and NaN encoding, They can be pinned independently.
+const OPTIONS: [Opt; 7] = [
+    Opt {
+        attr: "num",
+        module: "num",
+        values: NUM,
+        names: NUM,
+        sizes: true,
+    },
+    Opt {
+        attr: "len",
+        module: "len",
+        values: LEN,
+        names: LEN,
+        sizes: true,
+    },
+    Opt {
+        attr: "int_codec",
+        module: "int_codec",
+        values: INT_CODEC,
+        names: INT_CODEC,
+        sizes: true,
+    },
+    Opt {
+        attr: "char_codec",
+        module: "char_codec",
+        values: CHAR_CODEC,
+        names: CHAR_CODEC,
+        sizes: true,
+    },
+    Opt {
+        attr: "float",
+        module: "float_order",
+        values: &["NUM", "LE", "BE"],
+        names: &["LE", "BE"],
+        sizes: false,
+    },
+    Opt {
+        attr: "float",
+        module: "float_format",
+        values: &["FULL", "F16", "BF16"],
+        names: &["F16", "BF16"],
+        sizes: true,
+    },
+    Opt {
+        attr: "float",
+        module: "float_nan",
+        values: &["PRESERVE", "CANONICAL"],
+        names: &["CANONICAL_NAN"],
+        sizes: false,
+    },
+];
+
+/// Every option of [OPTIONS], As a set of their indices.
+const ALL: u8 = (1 << OPTIONS.len()) - 1;
+
+/// Options pinned by field attributes, e.g. `#[databuf(num = BE)]`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Pinned {
+    /// Pinned options, `(index of the option, index of its name)`.
+    values: Vec<(usize, usize)>,
+    /// Options that the type of the field reads from its config, As a set of indices of [OPTIONS].
+    reads: u8,
+}
+
+impl Pinned {
+    pub fn new(options: &[(Ident, Ident)], ty: &Type) -> Self {
+        let mut values: Vec<_> = options
+            .iter()
+            .map(|(attr, name)| {
+                find(attr, name).expect("options are checked by `FieldAttrs::parse`")
+            })
+            .collect();
+        values.sort();
+        let mut pinned = Self { values, reads: 0 };
+        pinned.reads = options_read_by(ty) & !pinned.pinned();
+        pinned
+    }
+
+    /// Name of the helper function, that dispatches the config of fields with these options.
+    pub fn helper_name(&self) -> Ident {
+        let mut name = String::from("__databuf_config");
+        for &(opt, idx) in &self.values {
+            let opt = &OPTIONS[opt];
+            name += &format!("_{}_{}", opt.attr, opt.names[idx].to_lowercase());
+        }
+        Ident::new(&format!("{name}_{:x}", self.reads), Span::call_site())
+    }
+
+    /// Helper function that calls `Visit::visit` with the config of the field,
+    /// The options that the type of the field reads are taken from `C`.
+    ///
+    /// `select` is evaluated at compile time, So only the selected `visit` is instantiated.
+    pub fn helper(&self, crate_path: &TokenStream, o: &mut TokenStream) {
+        let name = self.helper_name();
+        let select = quote(|o| {
+            let c = Ident::new("C", Span::call_site());
+            self.select(
+                crate_path,
+                self.reads,
+                c,
+                |config, o| {
+                    quote!(o, { V::visit::<#config> });
+                },
+                o,
+            );
+        });
+        quote!(o, {
+            #[inline]
+            fn #name<const C: u16, V: #crate_path::config::visit::Visit>(v: V) -> V::Output {
+                const fn select<const C: u16, V: #crate_path::config::visit::Visit>() -> fn(V) -> V::Output {
+                    #select
+                }
+                (const { select::<C, V>() })(v)
+            }
+        });
+    }
+
+    /// Calls the helper function of these options with `visitor`.
+    pub fn visit(&self, config: &TokenStream, visitor: impl IntoTokens, o: &mut TokenStream) {
+        let name = self.helper_name();
+        quote!(o, { #name::<#config, _>(#visitor) });
+    }
+
+    /// Function pointer of `path::<CONFIG>` with the config of the field.
+    ///
+    /// Custom codecs are not generic over a visitor, So each field selects its own function,
+    /// And they may read any option, Not only the ones of the type of the field.
+    pub fn select_fn(
+        &self,
+        crate_path: &TokenStream,
+        config: &TokenStream,
+        path: &Path,
+        o: &mut TokenStream,
+    ) {
+        let select = quote(|o| {
+            self.select(
+                crate_path,
+                ALL & !self.pinned(),
+                config,
+                |c, o| {
+                    quote!(o, { #path::<#c> });
+                },
+                o,
+            );
+        });
+        quote!(o, { (const { #select }) });
+    }
+
+    /// Same as [Pinned::select], But the options that don't change the encoded size are the defaults.
+    pub fn select_size(
+        &self,
+        crate_path: &TokenStream,
+        config: impl IntoTokens,
+        arm: impl Fn(TokenStream, &mut TokenStream),
+        o: &mut TokenStream,
+    ) {
+        let sizes = (0..OPTIONS.len())
+            .filter(|&opt| OPTIONS[opt].sizes)
+            .fold(0, |set, opt| set | 1 << opt);
+        self.select(crate_path, self.reads & sizes, config, arm, o)
+    }
+
+    /// Matches the options of `config` in `rest` against every value of them, One option at a time.
+    ///
+    /// `arm` receives the config of the field as a constant expression of `databuf::config` constants,
+    /// So the bits of the options are only defined by that module. Other options are the defaults.
+    fn select(
+        &self,
+        crate_path: &TokenStream,
+        rest: u8,
+        config: impl IntoTokens,
+        arm: impl Fn(TokenStream, &mut TokenStream),
+        o: &mut TokenStream,
+    ) {
+        let mut bits: Vec<TokenStream> = self
+            .values
+            .iter()
+            .map(|&(opt, idx)| {
+                let opt = &OPTIONS[opt];
+                constant(crate_path, opt.attr, opt.names[idx])
+            })
+            .collect();
+        let rest: Vec<_> = (0..OPTIONS.len())
+            .filter(|opt| rest & 1 << opt != 0)
+            .map(|opt| &OPTIONS[opt])
+            .collect();
+        let mut config_tokens = TokenStream::new();
+        config.into_tokens(&mut config_tokens);
+        select_each(crate_path, &rest, &config_tokens, &mut bits, &arm, o);
+    }
+
+    /// Set of the indices of pinned options.
+    fn pinned(&self) -> u8 {
+        self.values.iter().fold(0, |set, (opt, _)| set | 1 << opt)
+    }
+}
+
+/// Nested `match` of `rest` options, The innermost arms call `arm` with the config of the field.
+fn select_each(
+    crate_path: &TokenStream,
+    rest: &[&Opt],
+    config: &TokenStream,
+    bits: &mut Vec<TokenStream>,
+    arm: &impl Fn(TokenStream, &mut TokenStream),
+    o: &mut TokenStream,
+) {
+    let Some((opt, rest)) = rest.split_first() else {
+        let bits = quote(|o| {
+            for (i, value) in bits.iter().enumerate() {
+                if i > 0 {
+                    quote!(o, { | });
+                }
+                quote!(o, { #value });
+            }
+        });
+        let mut tokens = TokenStream::new();
+        quote!(tokens, { { #bits } });
+        return arm(tokens, o);
+    };
+    let get = constant(crate_path, opt.module, "GET");
+    let arms = quote(|o| {
+        for value in opt.values {
+            let key = constant(crate_path, opt.module, value);
+            bits.push(key.clone());
+            let inner = quote(|o| select_each(crate_path, rest, config, bits, arm, o));
+            quote!(o, { #key => #inner, });
+            bits.pop();
+        }
+    });
+    quote!(o, {
+        match #config & #get {
+            #arms
+            _ => ::core::unreachable!(),
+        }
+    });
+}
+
+/// Path of `databuf::config::#module::#name` constant.
+fn constant(crate_path: &TokenStream, module: &str, name: &str) -> TokenStream {
+    let module = Ident::new(module, Span::call_site());
+    let name = Ident::new(name, Span::call_site());
+    let mut tokens = TokenStream::new();
+    quote!(tokens, { #crate_path::config::#module::#name });
+    tokens
+}
+
+/// Options that `ty` reads from its config, As a set of indices of [OPTIONS].
+///
+/// Types of this crate are matched by name, Every other type may read any option.
+fn options_read_by(ty: &Type) -> u8 {
+    const NUM: u8 = 1;
+    // Fixed-width lengths are in the byte order of `num` option.
+    const LEN: u8 = 1 << 1 | NUM;
+    const INT_CODEC: u8 = 1 << 2 | NUM;
+    const CHAR_CODEC: u8 = 1 << 3 | NUM;
+    // `float_order::NUM` is the byte order of `num` option.
+    const FLOAT: u8 = 0b111 << 4 | NUM;
+
+    match ty {
+        Type::Paren(ty) => options_read_by(&ty.elem),
+        Type::Group(ty) => options_read_by(&ty.elem),
+        Type::Reference(ty) => options_read_by(&ty.elem),
+        Type::Array(ty) => options_read_by(&ty.elem),
+        Type::Slice(ty) => LEN | options_read_by(&ty.elem),
+        Type::Tuple(ty) => ty.elems.iter().fold(0, |set, ty| set | options_read_by(ty)),
+        Type::Path(TypePath { qself: None, path }) => {
+            let Some(last) = path.segments.last() else {
+                return ALL;
+            };
+            let args: Vec<&Type> = match &last.arguments {
+                PathArguments::None => Vec::new(),
+                PathArguments::AngleBracketed(args) => args
+                    .args
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    })
+                    .collect(),
+                PathArguments::Parenthesized(_) => return ALL,
+            };
+            let of_args = args.iter().fold(0, |set, ty| set | options_read_by(ty));
+            match (last.ident.to_string().as_str(), args.len()) {
+                ("bool" | "u8", 0) => 0,
+                ("u16" | "u32" | "u64" | "u128" | "usize", 0) => NUM,
+                ("i8" | "i16" | "i32" | "i64" | "i128" | "isize", 0) => INT_CODEC,
+                ("f32" | "f64", 0) => FLOAT,
+                ("char", 0) => CHAR_CODEC,
+                ("str" | "String", 0) => LEN,
+                ("PhantomData", 1) => 0,
+                ("Box" | "Rc" | "Arc" | "Cell" | "RefCell" | "Cow" | "Option", 1) => of_args,
+                (
+                    "Vec" | "VecDeque" | "LinkedList" | "BinaryHeap" | "BTreeSet" | "HashSet"
+                    | "BTreeMap" | "HashMap",
+                    1..,
+                ) => LEN | of_args,
+                _ => ALL,
+            }
+        }
+        _ => ALL,
+    }
+}
+
+/// Index of `#[databuf(#attr = #name)]` option and of its name, `(option, name)`.
+pub fn find(attr: &Ident, name: &Ident) -> Option<(usize, usize)> {
+    OPTIONS
+        .iter()
+        .enumerate()
+        .filter(|(_, opt)| attr == opt.attr)
+        .find_map(|(i, opt)| Some((i, opt.names.iter().position(|n| name == n)?)))
+}
+
+/// Names of the values of `attr` option, e.g. "`LE`, `BE`".
+pub fn names_of(attr: &Ident) -> String {
+    let names: Vec<_> = OPTIONS
+        .iter()
+        .filter(|opt| attr == opt.attr)
+        .flat_map(|opt| opt.names.iter().map(|name| format!("`{name}`")))
+        .collect();
+    names.join(", ")
+}
@@ -2,9 +2,27 @@ use super::*;
 
 impl Expand<'_, '_> {
     pub fn decoder(&mut self) {
+        let config = &self.config("C");
+        let bit_order = &self.bit_order();
         let crate_path = &self.crate_path;
-        let enum_repr = &self.enum_repr;
-        let is_unit_enum = &self.is_unit_enum;
+        let discriminant_ty = &self.discriminant();
+        let where_clause = self.where_clause(
+            parse_quote!(#crate_path::Decode<'decode>),
+            parse_quote!(#crate_path::bits::DecodeBits),
+            Impl::Decode,
+        );
+        let reader_where_clause = self.where_clause(
+            parse_quote!(#crate_path::DecodeFromReader),
+            parse_quote!(#crate_path::bits::DecodeBits),
+            Impl::DecodeFromReader,
+        );
+        let helpers = self.helpers(true);
+        // Custom decoders only decode from slice, Unless `with` module opts in with `from_reader`,
+        // So such types don't implement `DecodeFromReader`.
+        let from_reader = self.fields().iter().all(|f| {
+            let attrs = attr::parse(&f.attrs, FieldAttrs::parse);
+            attrs.decode_with.is_none() && (attrs.with.is_none() || attrs.from_reader)
+        });
         let output = &mut self.output;
         let DeriveInput {
             data,
@@ -13,113 +31,361 @@ impl Expand<'_, '_> {
             ..
         } = self.input;
 
-        let body = quote(|o| {
-            match data {
-                Data::Struct(v) => {
-                    let de = decode_fields(&v.fields);
-                    quote!(o, { let output = Self #de });
-                }
-                Data::Enum(enum_data) => {
-                    let items = quote(|o| {
-                        let mut discriminator = Discriminator::new(true);
-                        for Variant {
-                            ident,
-                            fields,
-                            discriminant,
-                            ..
-                        } in enum_data.variants.iter()
-                        {
-                            let index = discriminator.get(discriminant);
-                            let fields = decode_fields(fields);
-                            quote!(o, {
-                                #index => Self::#ident #fields,
-                            });
-                        }
-                    });
+        let root = &ident.to_string();
+        let decode = Ident::new("decode_limited", Span::call_site());
+        let decode_from_reader = Ident::new("decode_from_reader_limited", Span::call_site());
+        let body = |method| {
+            quote(move |o| {
+                match data {
+                    Data::Struct(v) => {
+                        let ctx = Context {
+                            crate_path,
+                            config,
+                            bit_order,
+                            root,
+                            variant: None,
+                        };
+                        let de = decode_fields(ctx, &v.fields, method);
+                        quote!(o, { let output = { #de } });
+                    }
+                    Data::Enum(enum_data) => {
+                        let items = quote(|o| {
+                            let mut discriminator = Discriminator::new(true);
+                            for Variant {
+                                ident,
+                                fields,
+                                discriminant,
+                                ..
+                            } in enum_data.variants.iter()
+                            {
+                                let index = discriminator.get(discriminant);
+                                let ctx = Context {
+                                    crate_path,
+                                    config,
+                                    bit_order,
+                                    root,
+                                    variant: Some(ident),
+                                };
+                                let fields = decode_fields(ctx, fields, method);
+                                quote!(o, {
+                                    #index => { #fields }
+                                });
+                            }
+                        });
 
-                    let id = quote(|o| {
-                        let ty = match enum_repr {
-                            Some(repr) => repr,
-                            None if !is_unit_enum => {
+                        let id = quote(|o| match discriminant_ty {
+                            Discriminant::Repr(repr) => {
                                 quote!(o, {
-                                    let discriminant: u16 = #crate_path::var_int::BEU15::decode::<C>(c)?.0;
+                                    let discriminant: #repr = D::#method::<#config>(c, budget)
+                                        .map_err(|e| e.context(#root, &[]))?;
+                                });
+                            }
+                            Discriminant::BEU15 => {
+                                quote!(o, {
+                                    let discriminant: u16 = #crate_path::var_int::BEU15::#method::<#config>(c, budget)
+                                        .map_err(|e| e.context(#root, &[]))?.0;
+                                });
+                            }
+                            Discriminant::LEB128 => {
+                                quote!(o, {
+                                    let discriminant: u64 = D::#method::<{ #crate_path::config::num::LEB128 }>(c, budget)
+                                        .map_err(|e| e.context(#root, &[]))?;
                                 });
-                                return;
                             }
-                            None => "isize",
-                        };
-                        let repr = Ident::new(ty, Span::call_site());
-                        quote!(o, {
-                            let discriminant: #repr = D::decode::<C>(c)?;
                         });
-                    });
 
-                    let ident = ident.to_string();
-                    quote!(o, {
-                        #id
-                        let output = match discriminant {
-                            #items
-                            _ => {
-                                return #crate_path::error::UnknownDiscriminant::new_boxed_err(
-                                    ::std::concat!(::std::module_path!(), "::", #ident),
-                                    discriminant
-                                )
+                        let ident = ident.to_string();
+                        quote!(o, {
+                            #id
+                            let output = match discriminant {
+                                #items
+                                _ => {
+                                    return #crate_path::error::DecodeError::unknown_discriminant(
+                                        ::core::concat!(::core::module_path!(), "::", #ident),
+                                        discriminant as i128
+                                    )
+                                }
                             }
-                        }
-                    });
-                }
-                Data::Union(_) => {
-                    panic!("`Decode` implementation for `union` is not yet stabilized")
+                        });
+                    }
+                    Data::Union(_) => {
+                        unreachable!("`union` is rejected by `Expand::new`")
+                    }
+                };
+            })
+        };
+
+        let body_from_slice = body(&decode);
+        let (impl_generics, ty_generics, _) = &generics.split_for_impl();
+        let reader_impl = quote(|o| {
+            if !from_reader {
+                return;
+            }
+            let body_from_reader = body(&decode_from_reader);
+            quote!(o, {
+                impl #impl_generics #crate_path::DecodeFromReader for #ident #ty_generics #reader_where_clause {
+                    fn decode_from_reader_limited<const C: u16>(
+                        c: &mut (impl #crate_path::io::Read + ?::core::marker::Sized),
+                        budget: &mut #crate_path::limits::Budget,
+                    ) -> #crate_path::Result<Self> {
+                        use #crate_path::DecodeFromReader as D;
+                        let mut nested = budget.nested().map_err(|e| e.context(#root, &[]))?;
+                        let budget = &mut *nested;
+                        #body_from_reader;
+                        ::core::result::Result::Ok(output)
+                    }
                 }
-            };
+            });
         });
 
-        let (_, ty_generics, where_clause) = generics.split_for_impl();
-
-        // Add a bound `T: Decode<'de>` to every type parameter of `T`.
-        let bound: TypeParamBound = parse_quote!(#crate_path::Decode<'decode>);
-        let mut params = generics.params.clone();
+        // `'decode` outlives every lifetime of `T`.
+        let params = &generics.params;
         let mut lifetime = LifetimeParam::new(Lifetime::new("'decode", Span::call_site()));
+        for lt in generics.lifetimes() {
+            lifetime.bounds.push(lt.lifetime.clone());
+        }
 
-        for param in params.iter_mut() {
-            match param {
-                GenericParam::Type(ty) => ty.bounds.push(bound.clone()),
-                GenericParam::Lifetime(lt) => lifetime.bounds.push(lt.lifetime.clone()),
-                _ => {}
-            }
+        quote!(output, {
+            const _: () = {
+                #helpers
+                impl <#lifetime, #params> #crate_path::Decode<'decode> for #ident #ty_generics #where_clause {
+                    #[inline]
+                    fn decode<const C: u16>(c: &mut &'decode [u8]) -> #crate_path::Result<Self> {
+                        Self::decode_limited::<C>(c, &mut #crate_path::limits::Budget::unlimited())
+                    }
+                    fn decode_limited<const C: u16>(
+                        c: &mut &'decode [u8],
+                        budget: &mut #crate_path::limits::Budget,
+                    ) -> #crate_path::Result<Self> {
+                        use #crate_path::Decode as D;
+                        let mut nested = budget.nested().map_err(|e| e.context(#root, &[]))?;
+                        let budget = &mut *nested;
+                        #body_from_slice;
+                        ::core::result::Result::Ok(output)
+                    }
+                }
+                #reader_impl
+            };
+        });
+        if self.is_unit_enum {
+            self.decode_bits();
         }
+    }
+}
+
+impl Expand<'_, '_> {
+    /// `DecodeBits` of unit-only enum, So it can be a bit field.
+    fn decode_bits(&mut self) {
+        let crate_path = &self.crate_path;
+        let repr = Ident::new(
+            self.enum_repr.as_deref().unwrap_or("i32"),
+            Span::call_site(),
+        );
+        // Without `#[repr(...)]`, Discriminants are packed as unsigned.
+        let bits_repr = Ident::new(
+            self.enum_repr.as_deref().unwrap_or("u32"),
+            Span::call_site(),
+        );
+        let output = &mut self.output;
+        let DeriveInput {
+            data,
+            ident,
+            generics,
+            ..
+        } = self.input;
+        let Data::Enum(enum_data) = data else { return };
 
+        let items = quote(|o| {
+            let mut discriminator = Discriminator::new(true);
+            for Variant {
+                ident,
+                discriminant,
+                ..
+            } in &enum_data.variants
+            {
+                let index = discriminator.get(discriminant);
+                quote!(o, { #index => Self::#ident, });
+            }
+        });
+        let name = ident.to_string();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
         quote!(output, {
-            impl <#lifetime, #params> #crate_path::Decode<'decode> for #ident #ty_generics #where_clause {
-                fn decode<const C: u16>(c: &mut &'decode [u8]) -> #crate_path::Result<Self> {
-                    use #crate_path::Decode as D;
-                    #body;
-                    ::std::result::Result::Ok(output)
+            impl #impl_generics #crate_path::bits::DecodeBits for #ident #ty_generics #where_clause {
+                fn decode_bits(bits: u64, len: u32) -> #crate_path::Result<Self> {
+                    let discriminant = <#bits_repr as #crate_path::bits::DecodeBits>::decode_bits(bits, len)? as #repr;
+                    ::core::result::Result::Ok(match discriminant {
+                        #items
+                        _ => {
+                            return #crate_path::error::DecodeError::unknown_discriminant(
+                                ::core::concat!(::core::module_path!(), "::", #name),
+                                discriminant as i128
+                            )
+                        }
+                    })
                 }
             }
         });
     }
 }
 
-fn decode_fields(fields: &Fields) -> Token<impl FnOnce(&mut TokenStream) + '_> {
-    let expr = quote(|o| {
+/// Where the decoded fields are located, used to build the error path.
+struct Context<'a> {
+    crate_path: &'a TokenStream,
+    config: &'a TokenStream,
+    root: &'a str,
+    bit_order: &'a TokenStream,
+    variant: Option<&'a Ident>,
+}
+
+fn decode_fields<'a>(
+    ctx: Context<'a>,
+    fields: &'a Fields,
+    method: &'a Ident,
+) -> Token<impl FnOnce(&mut TokenStream) + 'a> {
+    let Context {
+        crate_path,
+        config,
+        bit_order,
+        root,
+        variant,
+    } = ctx;
+    // `.map_err(...)` that adds the path of the field to the error.
+    let map_err = move |field: &str, o: &mut TokenStream| {
+        let segment = quote(|o| {
+            quote!(o, { #crate_path::error::PathSegment });
+        });
+        let variant = quote(|o| {
+            if let Some(variant) = variant {
+                let variant = variant.unraw().to_string();
+                quote!(o, { #segment::Variant(#variant) });
+            }
+        });
         quote!(o, {
-            D::decode::<C>(c)?,
+            .map_err(|e| e.context(#root, &[#segment::Field(#field), #variant]))?
         });
-    });
-    quote(move |o: &mut TokenStream| match fields {
-        Fields::Named(fields) => {
-            let fields = quote(|o| {
-                for Field { ident, .. } in fields.named.iter() {
-                    quote!(o, { #ident: #expr });
-                }
+    };
+    quote(move |o: &mut TokenStream| {
+        let names: Vec<String> = fields
+            .iter()
+            .enumerate()
+            .map(|(idx, f)| match &f.ident {
+                Some(ident) => ident.unraw().to_string(),
+                None => idx.to_string(),
+            })
+            .collect();
+        let attrs: Vec<_> = fields
+            .iter()
+            .map(|f| attr::parse(&f.attrs, FieldAttrs::parse))
+            .collect();
+        let types: Vec<&Type> = fields.iter().map(|f| &f.ty).collect();
+
+        let mut idx = 0;
+        while idx < attrs.len() {
+            if attrs[idx].is_skipped() {
+                let local = Ident::new(&format!("__{idx}"), Span::call_site());
+                let value = quote(|o| attrs[idx].default_value(o));
+                quote!(o, { let #local = #value; });
+                idx += 1;
+                continue;
+            }
+            if attrs[idx].bits.is_none() {
+                let local = Ident::new(&format!("__{idx}"), Span::call_site());
+                let field = &names[idx];
+                let decoder = match method == "decode_from_reader_limited" {
+                    true => attrs[idx].reader_decoder(),
+                    false => attrs[idx].decoder(),
+                };
+                let value = quote(|o| {
+                    match (decoder, attrs[idx].pinned(types[idx])) {
+                        (Some(path), Some(pinned)) => {
+                            let select = quote(|o| pinned.select_fn(crate_path, config, &path, o));
+                            quote!(o, { #select(c) });
+                        }
+                        (Some(path), None) => {
+                            quote!(o, { #path::<#config>(c) });
+                        }
+                        (None, Some(pinned)) => {
+                            let visitor = quote(|o| {
+                                if method == "decode_from_reader_limited" {
+                                    quote!(o, { #crate_path::config::visit::ReaderDecoder(c, budget, ::core::marker::PhantomData) });
+                                } else {
+                                    quote!(o, { #crate_path::config::visit::Decoder(c, budget, ::core::marker::PhantomData) });
+                                }
+                            });
+                            pinned.visit(config, visitor, o);
+                        }
+                        (None, None) => {
+                            quote!(o, { D::#method::<#config>(c, budget) });
+                        }
+                    }
+                    map_err(field, o);
+                });
+                quote!(o, { let #local = #value; });
+                idx += 1;
+                continue;
+            }
+            // A run of bit fields, Its bytes are read at once.
+            // Skipped fields are not on the wire, So they don't end the run.
+            let start = idx;
+            while idx < attrs.len() && (attrs[idx].bits.is_some() || attrs[idx].is_skipped()) {
+                idx += 1;
+            }
+            while attrs[idx - 1].is_skipped() {
+                idx -= 1;
+            }
+            let run = start..idx;
+            let bits: u32 = attrs[run.clone()].iter().filter_map(|a| a.bits).sum();
+            let bytes = bits.div_ceil(8) as usize;
+            let first = quote(|o| map_err(&names[start], o));
+            quote!(o, {
+                let __bytes: [u8; #bytes] = D::#method::<#config>(c, budget) #first;
+                let __bytes = &mut &__bytes[..];
+                let mut __bits = #crate_path::bits::BitReader::new(#bit_order);
             });
-            quote!(o, {{ #fields }});
+            for i in run {
+                let local = Ident::new(&format!("__{i}"), Span::call_site());
+                if attrs[i].is_skipped() {
+                    let value = quote(|o| attrs[i].default_value(o));
+                    quote!(o, { let #local = #value; });
+                    continue;
+                }
+                let bits = attrs[i].bits.unwrap_or_default();
+                let err = quote(|o| map_err(&names[i], o));
+                quote!(o, { let #local = __bits.read(__bytes, #bits) #err; });
+            }
+            let last = quote(|o| map_err(&names[idx - 1], o));
+            quote!(o, { __bits.finish() #last; });
         }
-        Fields::Unnamed(fields) => {
-            let de = quote(|o| fields.unnamed.iter().for_each(|_| expr(o)));
-            quote!(o, {( #de )});
+
+        let path = quote(|o| {
+            quote!(o, { Self });
+            if let Some(variant) = variant {
+                quote!(o, { ::#variant });
+            }
+        });
+        let locals = quote(|o| {
+            for (idx, f) in fields.iter().enumerate() {
+                let local = Ident::new(&format!("__{idx}"), Span::call_site());
+                match &f.ident {
+                    Some(ident) => {
+                        quote!(o, { #ident: #local, });
+                    }
+                    None => {
+                        quote!(o, { #local, });
+                    }
+                }
+            }
+        });
+        match fields {
+            Fields::Named(_) => {
+                quote!(o, { #path { #locals } });
+            }
+            Fields::Unnamed(_) => {
+                quote!(o, { #path ( #locals ) });
+            }
+            Fields::Unit => {
+                quote!(o, { #path });
+            }
         }
-        Fields::Unit => {}
     })
 }
@@ -1,11 +1,25 @@
 use super::*;
 use syn::punctuated::Iter;
 
+/// Method of the `Encode` trait, whose body is being generated.
+#[derive(Clone, Copy)]
+enum Method {
+    Encode,
+    EncodedLen,
+}
+
 impl Expand<'_, '_> {
     pub fn encoder(&mut self) {
+        let config = &self.config("C");
+        let bit_order = &self.bit_order();
         let crate_path = &self.crate_path;
-        let enum_repr = self.enum_repr.as_ref();
-        let is_unit_enum = &self.is_unit_enum;
+        let discriminant_ty = &self.discriminant();
+        let where_clause = self.where_clause(
+            parse_quote!(#crate_path::Encode),
+            parse_quote!(#crate_path::bits::EncodeBits),
+            Impl::Encode,
+        );
+        let helpers = self.helpers(false);
         let output = &mut self.output;
         let DeriveInput {
             data,
@@ -14,120 +28,356 @@ impl Expand<'_, '_> {
             ..
         } = self.input;
 
-        let body = quote(|o| {
-            match data {
-                Data::Struct(object) => match &object.fields {
-                    Fields::Named(fields) => fields.named.iter().for_each(|f| {
-                        encode_field(f, field(&f.ident), o);
-                    }),
-                    Fields::Unnamed(fields) => {
-                        fields.unnamed.iter().enumerate().for_each(|(idx, f)| {
-                            encode_field(f, field(Index::from(idx)), o);
-                        })
+        let body = |method| {
+            quote(move |o| {
+                match data {
+                    Data::Struct(object) => {
+                        let mut encoder = Encoder::new(crate_path, config, bit_order, method);
+                        for (idx, f) in object.fields.iter().enumerate() {
+                            match &f.ident {
+                                Some(name) => encoder.field(f, field(name), o),
+                                None => encoder.field(f, field(Index::from(idx)), o),
+                            }
+                        }
+                        encoder.finish(o);
                     }
-                    Fields::Unit => {}
-                },
-                Data::Enum(enum_data) => {
-                    let items = quote(|o| {
-                        let mut discriminator = Discriminator::new(false);
-
-                        for Variant {
-                            ident,
-                            fields,
-                            discriminant,
-                            ..
-                        } in &enum_data.variants
-                        {
-                            let index = discriminator.get(discriminant);
-                            let mut encoders = Token(TokenStream::new());
-
-                            let alias = quote(|o| {
-                                match &fields {
-                                    Fields::Named(f) => {
-                                        let alias = make_alias(true, f.named.iter(), &mut encoders);
-                                        quote!(o, {{ #alias }});
+                    Data::Enum(enum_data) => {
+                        let items = quote(|o| {
+                            let mut discriminator = Discriminator::new(false);
+
+                            for Variant {
+                                ident,
+                                fields,
+                                discriminant,
+                                ..
+                            } in &enum_data.variants
+                            {
+                                let index = discriminator.get(discriminant);
+                                let mut encoders = Token(TokenStream::new());
+
+                                let alias = quote(|o| {
+                                    match &fields {
+                                        Fields::Named(f) => {
+                                            let encoder =
+                                                Encoder::new(crate_path, config, bit_order, method);
+                                            let alias = make_alias(
+                                                encoder,
+                                                true,
+                                                f.named.iter(),
+                                                &mut encoders,
+                                            );
+                                            quote!(o, {{ #alias }});
+                                        }
+                                        Fields::Unnamed(f) => {
+                                            let encoder =
+                                                Encoder::new(crate_path, config, bit_order, method);
+                                            let alias = make_alias(
+                                                encoder,
+                                                false,
+                                                f.unnamed.iter(),
+                                                &mut encoders,
+                                            );
+                                            quote!(o, {( #alias )});
+                                        }
+                                        Fields::Unit => {}
+                                    };
+                                });
+                                let encode_index = quote(|o| match discriminant_ty {
+                                    Discriminant::Repr(repr) => {
+                                        let index = quote(|o| {
+                                            quote!(o, { &(#index) });
+                                        });
+                                        call(method, config, repr, index, o);
                                     }
-                                    Fields::Unnamed(f) => {
-                                        let alias =
-                                            make_alias(false, f.unnamed.iter(), &mut encoders);
-                                        quote!(o, {( #alias )});
+                                    Discriminant::BEU15 => {
+                                        let index = quote(|o| {
+                                            quote!(o, { &#crate_path::var_int::BEU15(#index) });
+                                        });
+                                        call(
+                                            method,
+                                            config,
+                                            Ident::new("E", Span::call_site()),
+                                            index,
+                                            o,
+                                        );
                                     }
-                                    Fields::Unit => {}
-                                };
-                            });
-                            let encode_index = quote(|o| {
-                                let ty = match enum_repr {
-                                    None if !is_unit_enum => {
-                                        quote!(o, {
-                                            E::encode::<C>(&BEU15(#index), c)?;
+                                    Discriminant::LEB128 => {
+                                        let mut leb128 = TokenStream::new();
+                                        quote!(leb128, { { #crate_path::config::num::LEB128 } });
+                                        let index = quote(|o| {
+                                            quote!(o, { &(#index) });
                                         });
-                                        return;
+                                        call(
+                                            method,
+                                            &leb128,
+                                            Ident::new("u64", Span::call_site()),
+                                            index,
+                                            o,
+                                        );
                                     }
-                                    Some(repr) => repr,
-                                    None => "isize",
-                                };
-                                let repr = Ident::new(ty, Span::call_site());
+                                });
                                 quote!(o, {
-                                    #repr::encode::<C>(&(#index), c)?;
+                                    Self:: #ident #alias => {
+                                        #encode_index
+                                        #encoders
+                                    }
                                 });
-                            });
-                            quote!(o, {
-                                Self:: #ident #alias => {
-                                    #encode_index
-                                    #encoders
-                                }
-                            });
-                        }
-                    });
-                    if !is_unit_enum && enum_repr.is_none() {
+                            }
+                        });
                         quote!(o, {
-                            use #crate_path::var_int::BEU15;
+                            match self {
+                                #items
+                            }
                         });
                     }
-                    quote!(o, {
-                        match self {
-                            #items
-                        }
-                    });
-                }
-                Data::Union(_) => {
-                    panic!("`Encode` implementation for `union` is not yet stabilized")
+                    Data::Union(_) => {
+                        unreachable!("`union` is rejected by `Expand::new`")
+                    }
+                };
+            })
+        };
+        let encode_body = body(Method::Encode);
+        let encoded_len_body = body(Method::EncodedLen);
+
+        let (impl_generics, ty_generics, _) = generics.split_for_impl();
+        quote!(output, {
+            const _: () = {
+                #helpers
+                impl #impl_generics #crate_path::Encode for #ident #ty_generics #where_clause {
+                    fn encode<const C: u16>(&self, c: &mut (impl #crate_path::io::Write + ?::core::marker::Sized)) -> #crate_path::io::Result<()> {
+                        use #crate_path::Encode as E;
+                        #encode_body
+                        ::core::result::Result::Ok(())
+                    }
+                    fn encoded_len<const C: u16>(&self) -> usize {
+                        use #crate_path::Encode as E;
+                        let mut len = 0;
+                        #encoded_len_body
+                        len
+                    }
                 }
             };
         });
+        if self.is_unit_enum {
+            self.encode_bits();
+        }
+        self.max_encoded_size();
+    }
 
-        let (_, ty_generics, where_clause) = generics.split_for_impl();
+    /// `EncodeBits` of unit-only enum, So it can be a bit field.
+    fn encode_bits(&mut self) {
+        let crate_path = &self.crate_path;
+        let repr = Ident::new(
+            self.enum_repr.as_deref().unwrap_or("i32"),
+            Span::call_site(),
+        );
+        // Without `#[repr(...)]`, Discriminants are packed as unsigned.
+        let as_unsigned = self.enum_repr.is_none().then(|| {
+            let mut tokens = TokenStream::new();
+            quote!(tokens, { as u32 });
+            tokens
+        });
+        let output = &mut self.output;
+        let DeriveInput {
+            data,
+            ident,
+            generics,
+            ..
+        } = self.input;
+        let Data::Enum(enum_data) = data else { return };
 
-        let bound: TypeParamBound = parse_quote!(#crate_path::Encode);
-        let mut params = generics.params.clone();
+        let items = quote(|o| {
+            let mut discriminator = Discriminator::new(false);
+            for Variant {
+                ident,
+                discriminant,
+                ..
+            } in &enum_data.variants
+            {
+                let index = discriminator.get(discriminant);
+                quote!(o, { Self::#ident => #index, });
+            }
+        });
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        quote!(output, {
+            impl #impl_generics #crate_path::bits::EncodeBits for #ident #ty_generics #where_clause {
+                fn encode_bits(&self, len: u32) -> ::core::option::Option<u64> {
+                    let discriminant: #repr = match self {
+                        #items
+                    };
+                    #crate_path::bits::EncodeBits::encode_bits(&(discriminant #as_unsigned), len)
+                }
+            }
+        });
+    }
+
+    fn max_encoded_size(&mut self) {
+        let config = &self.config("__CONFIG");
+        let crate_path = &self.crate_path;
+        let discriminant_ty = &self.discriminant();
+        let params: &Vec<_> = &self
+            .input
+            .generics
+            .type_params()
+            .map(|p| &p.ident)
+            .collect();
+        let output = &mut self.output;
+        let DeriveInput {
+            data,
+            ident,
+            generics,
+            ..
+        } = self.input;
 
-        for param in params.iter_mut() {
-            if let GenericParam::Type(ty) = param {
-                ty.bounds.push(bound.clone())
+        let (_, ty_generics, _) = generics.split_for_impl();
+        let mut generics = generics.clone();
+        generics.params.push(parse_quote!(const __CONFIG: u16));
+
+        let mut bounds = Vec::new();
+        let body = quote(|o| match data {
+            Data::Struct(object) => {
+                bounds.extend(object.fields.iter());
+                let sum = sum_of_fields(crate_path, config, params, &object.fields);
+                quote!(o, { #sum });
             }
-        }
+            Data::Enum(enum_data) => {
+                let discriminant = quote(|o| match discriminant_ty {
+                    Discriminant::Repr(repr) => max_size_of(crate_path, config, repr, o),
+                    Discriminant::BEU15 => {
+                        let ty: Type = parse_quote!(#crate_path::var_int::BEU15);
+                        max_size_of(crate_path, config, &ty, o);
+                    }
+                    Discriminant::LEB128 => {
+                        let mut leb128 = TokenStream::new();
+                        quote!(leb128, { { #crate_path::config::num::LEB128 } });
+                        max_size_of(crate_path, &leb128, Ident::new("u64", Span::call_site()), o);
+                    }
+                });
+                let variants = quote(|o| {
+                    for Variant { fields, .. } in &enum_data.variants {
+                        bounds.extend(fields.iter());
+                        let sum = sum_of_fields(crate_path, config, params, fields);
+                        quote!(o, {
+                            let max = #crate_path::size::max(max, { #sum });
+                        });
+                    }
+                });
+                quote!(o, {
+                    let max = ::core::option::Option::Some(0);
+                    #variants
+                    #crate_path::size::add(#discriminant, max)
+                });
+            }
+            Data::Union(_) => {}
+        });
+        let body = {
+            let mut tokens = TokenStream::new();
+            body.into_tokens(&mut tokens);
+            tokens
+        };
 
+        // Fields with pinned options are bounded by every possible config, So they are not bounded here.
+        // Bit fields, skipped fields and fields with custom encoder don't use `MaxEncodedSize`.
+        let where_clause = generics.make_where_clause();
+        for Field { ty, attrs, .. } in bounds {
+            let attrs = attr::parse(attrs, FieldAttrs::parse);
+            if attrs.options.is_empty()
+                && attrs.bits.is_none()
+                && !attrs.is_skipped()
+                && attrs.encoder().is_none()
+            {
+                where_clause
+                    .predicates
+                    .push(parse_quote!(#ty: #crate_path::MaxEncodedSize<#config>));
+            }
+        }
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
         quote!(output, {
-            impl<#params> #crate_path::Encode for #ident #ty_generics #where_clause {
-                fn encode<const C: u16>(&self, c: &mut (impl ::std::io::Write + ?::std::marker::Sized)) -> ::std::io::Result<()> {
-                    use #crate_path::Encode as E;
-                    #body
-                    ::std::result::Result::Ok(())
-                }
+            impl #impl_generics #crate_path::MaxEncodedSize<__CONFIG> for #ident #ty_generics #where_clause {
+                const MAX_ENCODED_SIZE: ::core::option::Option<usize> = { #body };
             }
         });
     }
 }
 
+fn max_size_of(
+    crate_path: &TokenStream,
+    config: impl IntoTokens,
+    ty: impl IntoTokens,
+    o: &mut TokenStream,
+) {
+    quote!(o, {
+        <#ty as #crate_path::MaxEncodedSize<#config>>::MAX_ENCODED_SIZE
+    });
+}
+
+fn sum_of_fields<'a>(
+    crate_path: &'a TokenStream,
+    config: &'a TokenStream,
+    params: &'a [&Ident],
+    fields: &'a Fields,
+) -> Token<impl FnOnce(&mut TokenStream) + 'a> {
+    quote(move |o| {
+        quote!(o, {
+            let size = ::core::option::Option::Some(0);
+        });
+        let mut bits = 0;
+        for Field { ty, attrs, .. } in fields {
+            let attrs = attr::parse(attrs, FieldAttrs::parse);
+            if attrs.is_skipped() {
+                continue;
+            }
+            if let Some(len) = attrs.bits {
+                bits += len;
+                continue;
+            }
+            let run = bytes_of(std::mem::take(&mut bits));
+            let ty_size = quote(|o| {
+                // Size of custom encoder is unknown.
+                if attrs.encoder().is_some() {
+                    quote!(o, { ::core::option::Option::None });
+                    return;
+                }
+                let mut tokens = TokenStream::new();
+                ty.into_tokens(&mut tokens);
+                match attrs.pinned(ty) {
+                    // It would be bounded by every config of the field, So the size is unknown.
+                    Some(_) if uses_params(tokens, params) => {
+                        quote!(o, { ::core::option::Option::None });
+                    }
+                    Some(pinned) => pinned.select_size(
+                        crate_path,
+                        config,
+                        |c, o| max_size_of(crate_path, c, ty, o),
+                        o,
+                    ),
+                    None => max_size_of(crate_path, config, ty, o),
+                }
+            });
+            quote!(o, {
+                let size = #crate_path::size::add(size, ::core::option::Option::Some(#run));
+                let size = #crate_path::size::add(size, #ty_size);
+            });
+        }
+        let run = bytes_of(bits);
+        quote!(o, { #crate_path::size::add(size, ::core::option::Option::Some(#run)) });
+    })
+}
+
 fn make_alias<'a>(
+    mut encoder: Encoder<'a>,
     is_named: bool,
     fields: Iter<'a, Field>,
     encoders: &'a mut TokenStream,
 ) -> Token<impl FnOnce(&mut TokenStream) + 'a> {
     quote(move |o| {
         for (i, f) in fields.enumerate() {
-            let alias = Ident::new(&format!("_{i}"), Span::call_site());
-            encode_field(f, &alias, encoders);
+            let alias = if attr::parse(&f.attrs, FieldAttrs::parse).is_skipped() {
+                Ident::new("_", Span::call_site())
+            } else {
+                Ident::new(&format!("_{i}"), Span::call_site())
+            };
+            encoder.field(f, &alias, encoders);
             if is_named {
                 let name = &f.ident;
                 quote!(o, {
@@ -137,21 +387,179 @@ fn make_alias<'a>(
                 quote!(o, { #alias, });
             }
         }
+        encoder.finish(encoders);
     })
 }
 
+/// Number of bytes of a run of bit fields.
+fn bytes_of(bits: u32) -> usize {
+    bits.div_ceil(8) as usize
+}
+
+/// Encodes fields in order, Consecutive bit fields are packed together.
+struct Encoder<'a> {
+    crate_path: &'a TokenStream,
+    config: &'a TokenStream,
+    bit_order: &'a TokenStream,
+    method: Method,
+    /// Pending run of bit fields: `(value, bits)`
+    run: Vec<(TokenStream, u32)>,
+}
+
+impl<'a> Encoder<'a> {
+    fn new(
+        crate_path: &'a TokenStream,
+        config: &'a TokenStream,
+        bit_order: &'a TokenStream,
+        method: Method,
+    ) -> Self {
+        Self {
+            crate_path,
+            config,
+            bit_order,
+            method,
+            run: Vec::new(),
+        }
+    }
+
+    fn field(&mut self, f: &Field, field: impl IntoTokens, o: &mut TokenStream) {
+        let attrs = attr::parse(&f.attrs, FieldAttrs::parse);
+        // Skipped fields are not on the wire, So they don't end a run of bit fields either.
+        if attrs.is_skipped() {
+            return;
+        }
+        let Some(bits) = attrs.bits else {
+            self.finish(o);
+            return encode_field(self.crate_path, self.config, self.method, f, field, o);
+        };
+        let mut value = TokenStream::new();
+        let maybe_ref = match &f.ty {
+            Type::Reference(_) => None,
+            ty => Some(Token![&](ty.span())),
+        };
+        quote!(value, { #maybe_ref #field });
+        self.run.push((value, bits));
+    }
+
+    /// Ends the pending run of bit fields.
+    fn finish(&mut self, o: &mut TokenStream) {
+        if self.run.is_empty() {
+            return;
+        }
+        let run = std::mem::take(&mut self.run);
+        let Self {
+            crate_path,
+            bit_order,
+            ..
+        } = self;
+        match self.method {
+            Method::Encode => {
+                let writes = quote(|o| {
+                    for (value, bits) in run {
+                        quote!(o, { __bits.write(c, #value, #bits)?; });
+                    }
+                });
+                quote!(o, {
+                    let mut __bits = #crate_path::bits::BitWriter::new(#bit_order);
+                    #writes
+                    __bits.finish()?;
+                });
+            }
+            Method::EncodedLen => {
+                let bytes = bytes_of(run.iter().map(|(_, bits)| bits).sum());
+                quote!(o, { len += #bytes; });
+            }
+        }
+    }
+}
+
 fn field(name: impl IntoTokens) -> Token<impl FnOnce(&mut TokenStream)> {
     quote(move |o| {
         quote!(o, { self.#name });
     })
 }
 
-fn encode_field(f: &Field, field: impl IntoTokens, o: &mut TokenStream) {
+fn encode_field(
+    crate_path: &TokenStream,
+    config: &TokenStream,
+    method: Method,
+    f: &Field,
+    field: impl IntoTokens,
+    o: &mut TokenStream,
+) {
     let maybe_ref = match &f.ty {
         Type::Reference(_) => None,
         ty => Some(Token![&](ty.span())),
     };
-    quote!(o, {
-        E::encode::<C>(#maybe_ref #field, c)?;
+    let value = quote(|o| {
+        quote!(o, { #maybe_ref #field });
     });
+    let attrs = attr::parse(&f.attrs, FieldAttrs::parse);
+    match (attrs.encoder(), attrs.pinned(&f.ty)) {
+        (Some(path), Some(pinned)) => {
+            let select = quote(|o| pinned.select_fn(crate_path, config, &path, o));
+            call_with(crate_path, method, select, value, o);
+        }
+        (Some(path), None) => {
+            let path = quote(|o| {
+                quote!(o, { #path::<#config> });
+            });
+            call_with(crate_path, method, path, value, o);
+        }
+        (None, Some(pinned)) => {
+            let visitor = quote(|o| match method {
+                Method::Encode => {
+                    quote!(o, { #crate_path::config::visit::Encoder(#value, c) });
+                }
+                Method::EncodedLen => {
+                    quote!(o, { #crate_path::config::visit::EncodedLen(#value) });
+                }
+            });
+            let visit = quote(|o| pinned.visit(config, visitor, o));
+            match method {
+                Method::Encode => {
+                    quote!(o, { #visit?; });
+                }
+                Method::EncodedLen => {
+                    quote!(o, { len += #visit; });
+                }
+            }
+        }
+        (None, None) => call(method, config, Ident::new("E", Span::call_site()), value, o),
+    }
+}
+
+/// Calls `#[databuf(with = ...)]` or `#[databuf(encode_with = ...)]` function, `encode` is `path::<CONFIG>`.
+fn call_with(
+    crate_path: &TokenStream,
+    method: Method,
+    encode: impl IntoTokens,
+    value: impl IntoTokens,
+    o: &mut TokenStream,
+) {
+    match method {
+        Method::Encode => {
+            quote!(o, { #encode(#value, c)?; });
+        }
+        Method::EncodedLen => {
+            quote!(o, { len += #crate_path::size::len_of(|c| #encode(#value, c)); });
+        }
+    }
+}
+
+fn call(
+    method: Method,
+    config: &TokenStream,
+    ty: impl IntoTokens,
+    value: impl IntoTokens,
+    o: &mut TokenStream,
+) {
+    match method {
+        Method::Encode => {
+            quote!(o, { #ty::encode::<#config>(#value, c)?; });
+        }
+        Method::EncodedLen => {
+            quote!(o, { len += #ty::encoded_len::<#config>(#value); });
+        }
+    }
 }
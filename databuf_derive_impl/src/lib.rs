@@ -1,3 +1,5 @@
+mod attr;
+mod config;
 mod decode;
 mod encode;
 
@@ -5,9 +7,11 @@ pub use quote2;
 pub use quote2::proc_macro2;
 pub use syn;
 
+use attr::{FieldAttrs, Impl, TypeAttrs};
+use config::Pinned;
 use proc_macro2::*;
 use quote2::{quote, IntoTokens, Quote, Token};
-use syn::{spanned::Spanned, *};
+use syn::{ext::IdentExt, spanned::Spanned, *};
 
 pub fn get_enum_repr(attrs: &Vec<Attribute>) -> Option<String> {
     for Attribute { meta, .. } in attrs {
@@ -42,6 +46,7 @@ pub struct Expand<'i, 'o> {
     pub output: &'o mut TokenStream,
     pub enum_repr: Option<String>,
     pub is_unit_enum: bool,
+    pub attrs: TypeAttrs,
 }
 
 impl<'i, 'o> Expand<'i, 'o> {
@@ -49,15 +54,288 @@ impl<'i, 'o> Expand<'i, 'o> {
         crate_path: TokenStream,
         input: &'i DeriveInput,
         output: &'o mut TokenStream,
-    ) -> Self {
-        Self {
+    ) -> Result<Self> {
+        let attrs = TypeAttrs::parse(&input.attrs)?;
+        validate(input, &attrs)?;
+        // `#[databuf(crate = ...)]` overrides the default crate path.
+        let crate_path = match &attrs.crate_path {
+            Some(path) => {
+                let mut tokens = TokenStream::new();
+                path.into_tokens(&mut tokens);
+                tokens
+            }
+            None => crate_path,
+        };
+        Ok(Self {
             crate_path,
             input,
             output,
             enum_repr: get_enum_repr(&input.attrs),
             is_unit_enum: is_unit_enum(input),
+            attrs,
+        })
+    }
+
+    /// Config of the fields, It is `generic` unless pinned with `#[databuf(config = ...)]`.
+    fn config(&self, generic: &str) -> TokenStream {
+        let mut config = TokenStream::new();
+        match &self.attrs.config {
+            Some(expr) => {
+                let crate_path = &self.crate_path;
+                quote!(config, {{
+                    use #crate_path::config::{num, len, int_codec, char_codec, float};
+                    #expr
+                }});
+            }
+            None => Ident::new(generic, Span::call_site()).into_tokens(&mut config),
+        }
+        config
+    }
+
+    /// Encoding of enum discriminants, Set with `#[databuf(discriminant = ...)]`.
+    ///
+    /// Defaults to `#[repr(...)]` type, Otherwise `BEU15` for enums with fields and `i32` for unit-only enums,
+    /// So the encoding doesn't depend on the pointer width of the host.
+    fn discriminant(&self) -> Discriminant {
+        match self
+            .attrs
+            .discriminant
+            .as_ref()
+            .map(Ident::to_string)
+            .as_deref()
+        {
+            Some("U8") => Discriminant::Repr(Ident::new("u8", Span::call_site())),
+            Some("LEB128") => Discriminant::LEB128,
+            Some(_) => Discriminant::BEU15,
+            None => match &self.enum_repr {
+                Some(repr) => Discriminant::Repr(Ident::new(repr, Span::call_site())),
+                None if !self.is_unit_enum => Discriminant::BEU15,
+                None => Discriminant::Repr(Ident::new("i32", Span::call_site())),
+            },
+        }
+    }
+
+    /// Where clause of the impl, Encoded fields must implement `bound`, Bit fields `bits_bound`.
+    ///
+    /// Set with `#[databuf(bound = "...")]` or `#[databuf(bound(encode = "...", decode = "..."))]`
+    /// on the type or on a field,
+    /// Otherwise it is inferred from the fields whose type uses a type parameter.
+    /// `DecodeFromReader` is also bounded by the fields whose type uses a lifetime parameter,
+    /// So borrowed fields make the impl unusable instead of failing to compile.
+    fn where_clause(
+        &self,
+        bound: TypeParamBound,
+        bits_bound: TypeParamBound,
+        of: Impl,
+    ) -> WhereClause {
+        let is_decoder = of != Impl::Encode;
+        let generics = &self.input.generics;
+        let mut where_clause = generics
+            .where_clause
+            .clone()
+            .unwrap_or_else(|| parse_quote!(where));
+        if let Some(predicates) = self.attrs.bound.get(of) {
+            where_clause.predicates.extend(predicates.iter().cloned());
+            return where_clause;
+        }
+        let mut params: Vec<_> = generics.type_params().map(|param| &param.ident).collect();
+        if of == Impl::DecodeFromReader {
+            params.extend(generics.lifetimes().map(|param| &param.lifetime.ident));
+        }
+        for Field { ty, attrs, .. } in self.fields() {
+            let attrs = attr::parse(attrs, FieldAttrs::parse);
+            if let Some(predicates) = attrs.bound.get(of) {
+                where_clause.predicates.extend(predicates.iter().cloned());
+                continue;
+            }
+            let mut tokens = TokenStream::new();
+            ty.into_tokens(&mut tokens);
+            if !uses_params(tokens, &params) {
+                continue;
+            }
+            let custom = match is_decoder {
+                true => attrs.decoder(),
+                false => attrs.encoder(),
+            };
+            if attrs.is_skipped() {
+                if is_decoder && attrs.default.is_none() {
+                    where_clause
+                        .predicates
+                        .push(parse_quote!(#ty: ::core::default::Default));
+                }
+            } else if attrs.bits.is_some() {
+                where_clause.predicates.push(parse_quote!(#ty: #bits_bound));
+            } else if custom.is_none() {
+                where_clause.predicates.push(parse_quote!(#ty: #bound));
+            }
+        }
+        where_clause
+    }
+
+    /// Fields of the struct, Or of every variant of the enum.
+    fn fields(&self) -> Vec<&'i Field> {
+        match &self.input.data {
+            Data::Struct(object) => object.fields.iter().collect(),
+            Data::Enum(data) => data.variants.iter().flat_map(|v| &v.fields).collect(),
+            Data::Union(_) => Vec::new(),
+        }
+    }
+
+    /// Helper functions of the fields with pinned options, One per distinct set of options.
+    ///
+    /// Fields with custom encoder or decoder don't use them.
+    fn helpers(&self, is_decoder: bool) -> TokenStream {
+        let mut pinned = std::collections::BTreeSet::new();
+        for Field { attrs, ty, .. } in self.fields() {
+            let attrs = attr::parse(attrs, FieldAttrs::parse);
+            let custom = match is_decoder {
+                true => attrs.decoder(),
+                false => attrs.encoder(),
+            };
+            if custom.is_none() && !attrs.is_skipped() {
+                pinned.extend(attrs.pinned(ty));
+            }
+        }
+        let mut tokens = TokenStream::new();
+        for p in pinned {
+            p.helper(&self.crate_path, &mut tokens);
+        }
+        tokens
+    }
+
+    /// Order of bit fields, Set with `#[databuf(bit_order = ...)]`.
+    fn bit_order(&self) -> TokenStream {
+        let crate_path = &self.crate_path;
+        let order = match &self.attrs.bit_order {
+            Some(order) => order.clone(),
+            None => Ident::new("MsbFirst", Span::call_site()),
+        };
+        let mut tokens = TokenStream::new();
+        quote!(tokens, { #crate_path::bits::BitOrder::#order });
+        tokens
+    }
+}
+
+/// Checks the input and its `#[databuf(...)]` attributes, So the expansion never fails.
+fn validate(input: &DeriveInput, attrs: &TypeAttrs) -> Result<()> {
+    let mut errors = Vec::new();
+    let groups: Vec<&Fields> = match &input.data {
+        Data::Struct(object) => {
+            if let Some(discriminant) = &attrs.discriminant {
+                errors.push(Error::new(
+                    discriminant.span(),
+                    "`discriminant` is only supported on enums",
+                ));
+            }
+            vec![&object.fields]
+        }
+        Data::Enum(data) => {
+            if let Some(transparent) = &attrs.transparent {
+                errors.push(Error::new(
+                    transparent.span(),
+                    "`transparent` is only supported on structs",
+                ));
+            }
+            for v in &data.variants {
+                for attr in v
+                    .attrs
+                    .iter()
+                    .filter(|attr| attr.path().is_ident("databuf"))
+                {
+                    errors.push(Error::new_spanned(
+                        attr,
+                        "`#[databuf(...)]` is not supported on enum variants",
+                    ));
+                }
+            }
+            data.variants.iter().map(|v| &v.fields).collect()
+        }
+        Data::Union(data) => {
+            return Err(Error::new_spanned(
+                data.union_token,
+                "`union` is not supported, Use a struct or an enum",
+            ));
+        }
+    };
+    let mut encoded = Vec::new();
+    for fields in groups {
+        // Run of bit fields: `(last field, bits)`
+        let mut run = None;
+        for f in fields {
+            match FieldAttrs::parse(&f.attrs) {
+                Ok(attrs) if attrs.is_skipped() => {}
+                Ok(attrs) => {
+                    match attrs.bits {
+                        Some(bits) => run = Some((f, run.map_or(0, |(_, sum)| sum) + bits)),
+                        None => check_run(run.take(), &mut errors),
+                    }
+                    encoded.push((f, attrs));
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+        check_run(run, &mut errors);
+    }
+    if let (Some(transparent), Data::Struct(_), true) =
+        (&attrs.transparent, &input.data, errors.is_empty())
+    {
+        match &encoded[..] {
+            [(f, attrs)] if attrs.bits.is_some() => errors.push(Error::new_spanned(
+                f,
+                "`transparent` field is encoded as is, It can't be a bit field",
+            )),
+            [_] => {}
+            _ => errors.push(Error::new(
+                transparent.span(),
+                "`transparent` struct must have exactly one non-skipped field",
+            )),
+        }
+        if let Some(config) = &attrs.config {
+            errors.push(Error::new_spanned(
+                config,
+                "`transparent` struct uses the config of the caller, It can't pin `config`",
+            ));
         }
     }
+    let mut errors = errors.into_iter();
+    match errors.next() {
+        Some(mut err) => {
+            err.extend(errors);
+            Err(err)
+        }
+        None => Ok(()),
+    }
+}
+
+/// Rejects a run of bit fields that doesn't end on a byte boundary.
+fn check_run(run: Option<(&Field, u32)>, errors: &mut Vec<Error>) {
+    if let Some((last, bits)) = run.filter(|(_, bits)| bits % 8 != 0) {
+        errors.push(Error::new_spanned(
+            last,
+            format!(
+                "bit fields must add up to whole bytes, This run of bit fields has {bits} bits"
+            ),
+        ));
+    }
+}
+
+/// Whether `tokens` mention any of the type `params`.
+fn uses_params(tokens: TokenStream, params: &[&Ident]) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        TokenTree::Ident(ident) => params.iter().any(|param| ident == **param),
+        TokenTree::Group(group) => uses_params(group.stream(), params),
+        _ => false,
+    })
+}
+
+/// Encoding of enum discriminants.
+enum Discriminant {
+    /// Integer type, Encoded with the config of the caller.
+    Repr(Ident),
+    /// `var_int::BEU15`, Encoded with the config of the caller.
+    BEU15,
+    /// Unsigned LEB128, Whatever the config of the caller is.
+    LEB128,
 }
 
 struct Discriminator {
@@ -0,0 +1,177 @@
+//! Encode and decode over [futures_io::AsyncWrite] and [futures_io::AsyncRead].
+//!
+//! Enabled with the `async` feature. For tokio types, use the `compat` adapters of `tokio-util`.
+//!
+//! - [AsyncEncode] is implemented for every [Encode] type.
+//! - [AsyncDecoder] reads from an [AsyncRead] until a whole value can be decoded,
+//!   And keeps the remaining bytes for the next value.
+//!   [AsyncDecoder::with_limits] bounds the buffered bytes with [Limits::max_frame].
+//!
+//! ### Example
+//!
+//! ```
+//! use databuf::{*, async_io::*, config::num::LE};
+//!
+//! #[derive(Encode, Decode, PartialEq, Debug)]
+//! struct Msg {
+//!     id: u16,
+//!     data: String,
+//! }
+//!
+//! # fn block_on<F: std::future::Future>(fut: F) -> F::Output {
+//! #     let mut fut = std::pin::pin!(fut);
+//! #     let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+//! #     loop {
+//! #         if let std::task::Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+//! #             return out;
+//! #         }
+//! #     }
+//! # }
+//! block_on(async {
+//!     let mut socket = Vec::new();
+//!     Msg { id: 1, data: "Hello".into() }.encode_async::<LE>(&mut socket).await.unwrap();
+//!     Msg { id: 2, data: "World".into() }.encode_async::<LE>(&mut socket).await.unwrap();
+//!
+//!     let mut decoder = AsyncDecoder::new(&socket[..]);
+//!     assert_eq!(decoder.decode::<Msg, LE>().await.unwrap(), Msg { id: 1, data: "Hello".into() });
+//!     assert_eq!(decoder.decode::<Msg, LE>().await.unwrap(), Msg { id: 2, data: "World".into() });
+//! });
+//! ```
+
+use crate::{
+    error::{ErrorKind, Limit},
+    limits::Budget,
+    *,
+};
+use core::{future::poll_fn, future::Future, pin::Pin};
+pub use futures_io::{AsyncRead, AsyncWrite};
+
+/// Encode the value into an [AsyncWrite] sink.
+///
+/// This trait is automatically implemented for any type that implements the [Encode] trait.
+pub trait AsyncEncode: Encode {
+    /// Encode the value into a buffer, Then write it to `writer`.
+    fn encode_async<const CONFIG: u16>(
+        &self,
+        writer: &mut (impl AsyncWrite + Unpin + ?Sized),
+    ) -> impl Future<Output = io::Result<()>> {
+        let bytes = self.to_bytes::<CONFIG>();
+        async move { write_all(writer, &bytes).await }
+    }
+}
+
+impl<T: Encode + ?Sized> AsyncEncode for T {}
+
+async fn write_all(
+    writer: &mut (impl AsyncWrite + Unpin + ?Sized),
+    mut buf: &[u8],
+) -> io::Result<()> {
+    while !buf.is_empty() {
+        match poll_fn(|cx| Pin::new(&mut *writer).poll_write(cx, buf)).await {
+            Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+            Ok(n) => buf = &buf[n..],
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    poll_fn(|cx| Pin::new(&mut *writer).poll_flush(cx)).await
+}
+
+/// Decode values from an [AsyncRead] source.
+///
+/// Bytes are read as they are required, And bytes that are read past the decoded value are kept for the next call.
+pub struct AsyncDecoder<R> {
+    reader: R,
+    buf: Vec<u8>,
+    pos: usize,
+    limits: Limits,
+}
+
+impl<R: AsyncRead + Unpin> AsyncDecoder<R> {
+    /// Creates a new decoder, Without any [Limits].
+    pub fn new(reader: R) -> Self {
+        Self::with_limits(reader, Limits::UNLIMITED)
+    }
+
+    /// Creates a new decoder, That decodes every value within `limits`.
+    ///
+    /// The buffer never grows past [Limits::max_frame].
+    pub fn with_limits(reader: R, limits: Limits) -> Self {
+        Self {
+            reader,
+            buf: Vec::new(),
+            pos: 0,
+            limits,
+        }
+    }
+
+    /// Bytes that are read, But not decoded yet.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..]
+    }
+
+    /// Unwraps this decoder, Returning the underlying reader.
+    ///
+    /// Any bytes in the [AsyncDecoder::buffer] are lost.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Decode the next value.
+    ///
+    /// Fails with [InsufficientBytes](ErrorKind::InsufficientBytes) error, If the reader reached EOF before a whole value was read.
+    /// Or with [LimitExceeded](ErrorKind::LimitExceeded) error, If the value needs more than [Limits::max_frame] bytes.
+    pub async fn decode<T: DecodeOwned, const CONFIG: u16>(&mut self) -> Result<T> {
+        loop {
+            let bytes = &self.buf[self.pos..];
+            let mut rest = bytes;
+            match T::decode_limited::<CONFIG>(&mut rest, &mut Budget::new(self.limits)) {
+                Ok(value) => {
+                    self.pos = self.buf.len() - rest.len();
+                    return Ok(value);
+                }
+                Err(err) => {
+                    let err = err.at(bytes.len() - rest.len());
+                    let ErrorKind::InsufficientBytes { missing } = err.kind() else {
+                        return Err(err);
+                    };
+                    let target = bytes.len().saturating_add(missing.unwrap_or(1));
+                    if target > self.limits.max_frame {
+                        return Err(Error::new(ErrorKind::LimitExceeded(Limit::Frame)));
+                    }
+                    if !self.fill_buf(target).await? {
+                        return Err(err);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Read until `target` bytes are buffered, Returns `false` at EOF.
+    ///
+    /// `target` is untrusted, So the buffer only grows as bytes arrive.
+    /// The read size grows with the buffered bytes, So a large value is decoded only a few times,
+    /// But the buffer never grows past [Limits::max_frame].
+    async fn fill_buf(&mut self, target: usize) -> io::Result<bool> {
+        const MIN_READ: usize = 8 * 1024;
+        if self.pos > 0 {
+            self.buf.drain(..self.pos);
+            self.pos = 0;
+        }
+        while self.buf.len() < target {
+            let len = self.buf.len();
+            let read = len.max(MIN_READ).min(self.limits.max_frame - len);
+            self.buf.resize(len + read, 0);
+            let result =
+                poll_fn(|cx| Pin::new(&mut self.reader).poll_read(cx, &mut self.buf[len..])).await;
+            self.buf.truncate(len + *result.as_ref().unwrap_or(&0));
+            match result {
+                Ok(0) => return Ok(false),
+                Ok(_) => {}
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(true)
+    }
+}
@@ -0,0 +1,249 @@
+//! Bit-level fields, packed into bytes.
+//!
+//! [BitWriter] and [BitReader] pack consecutive fields of a few bits each, Like the headers of IPv4, TCP, DNS etc..
+//! A run of bit fields must end on a byte boundary.
+//!
+//! `#[derive(Encode, Decode)]` packs consecutive fields marked with `#[databuf(bits = N)]`, Runs that don't add up to whole bytes fail to compile,
+//! Fields are packed [BitOrder::MsbFirst] by default, Use `#[databuf(bit_order = LsbFirst)]` on the type to change it.
+//! Integers, `bool` and unit-only enums (that derive `Encode` / `Decode`) can be bit fields.
+//!
+//! ### Example
+//!
+//! ```
+//! use databuf::{*, config::num::BE};
+//!
+//! #[derive(Encode, Decode, PartialEq, Debug)]
+//! enum Kind { Query, Response }
+//!
+//! #[derive(Encode, Decode, PartialEq, Debug)]
+//! struct Header {
+//!     id: u16,
+//!     #[databuf(bits = 1)]
+//!     kind: Kind,
+//!     #[databuf(bits = 4)]
+//!     opcode: u8,
+//!     #[databuf(bits = 3)]
+//!     flags: u8,
+//! }
+//!
+//! let header = Header { id: 42, kind: Kind::Response, opcode: 2, flags: 0b101 };
+//! let bytes = header.to_bytes::<BE>();
+//! assert_eq!(bytes, [0, 42, 0b1_0010_101]);
+//! assert_eq!(Header::from_bytes::<BE>(&bytes).unwrap(), header);
+//! ```
+
+use crate::*;
+
+/// Order in which consecutive bit fields are packed into bytes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BitOrder {
+    /// (Default) The first field is packed into the most significant bits, As in network protocols.
+    #[default]
+    MsbFirst,
+    /// The first field is packed into the least significant bits.
+    LsbFirst,
+}
+
+/// A value that can be encoded in a few bits.
+pub trait EncodeBits {
+    /// Returns the value in `len` bits, `None` if the value doesn't fit in `len` bits.
+    fn encode_bits(&self, len: u32) -> Option<u64>;
+}
+
+/// A value that can be decoded from a few bits.
+pub trait DecodeBits: Sized {
+    /// Decode the value from `len` bits.
+    fn decode_bits(bits: u64, len: u32) -> Result<Self>;
+}
+
+#[inline]
+const fn mask(len: u32) -> u128 {
+    (1 << len) - 1
+}
+
+/// Writes bit fields, Complete bytes are written as soon as they are packed.
+#[derive(Debug, Default)]
+pub struct BitWriter {
+    order: BitOrder,
+    bits: u128,
+    len: u32,
+}
+
+impl BitWriter {
+    /// Creates a new writer.
+    pub fn new(order: BitOrder) -> Self {
+        Self {
+            order,
+            bits: 0,
+            len: 0,
+        }
+    }
+
+    /// Write `value` in `len` bits, `len` must be in `1..=64`.
+    ///
+    /// Fails with [IntegerOverflow](error::IntegerOverflow) error, If the value doesn't fit in `len` bits.
+    pub fn write(
+        &mut self,
+        c: &mut (impl Write + ?Sized),
+        value: &(impl EncodeBits + ?Sized),
+        len: u32,
+    ) -> io::Result<()> {
+        debug_assert!((1..=64).contains(&len));
+        let bits = value
+            .encode_bits(len)
+            .ok_or_else(|| utils::invalid_input(error::IntegerOverflow))?
+            as u128;
+        match self.order {
+            BitOrder::MsbFirst => self.bits = self.bits << len | bits,
+            BitOrder::LsbFirst => self.bits |= bits << self.len,
+        }
+        self.len += len;
+        while self.len >= 8 {
+            self.len -= 8;
+            let byte = match self.order {
+                BitOrder::MsbFirst => {
+                    let byte = (self.bits >> self.len) as u8;
+                    self.bits &= mask(self.len);
+                    byte
+                }
+                BitOrder::LsbFirst => {
+                    let byte = self.bits as u8;
+                    self.bits >>= 8;
+                    byte
+                }
+            };
+            c.write_all(&[byte])?;
+        }
+        Ok(())
+    }
+
+    /// Ends the run of bit fields, Fails if it doesn't end on a byte boundary.
+    pub fn finish(self) -> io::Result<()> {
+        if self.len == 0 {
+            Ok(())
+        } else {
+            Err(utils::invalid_input(
+                "bit fields don't end on a byte boundary",
+            ))
+        }
+    }
+}
+
+/// Reads bit fields, Bytes are read as they are required.
+#[derive(Debug, Default)]
+pub struct BitReader {
+    order: BitOrder,
+    bits: u128,
+    len: u32,
+}
+
+impl BitReader {
+    /// Creates a new reader.
+    pub fn new(order: BitOrder) -> Self {
+        Self {
+            order,
+            bits: 0,
+            len: 0,
+        }
+    }
+
+    /// Read a value of `len` bits, `len` must be in `1..=64`.
+    pub fn read<T: DecodeBits>(&mut self, c: &mut &[u8], len: u32) -> Result<T> {
+        debug_assert!((1..=64).contains(&len));
+        while self.len < len {
+            let byte = u8::decode::<{ config::DEFAULT }>(c)? as u128;
+            match self.order {
+                BitOrder::MsbFirst => self.bits = self.bits << 8 | byte,
+                BitOrder::LsbFirst => self.bits |= byte << self.len,
+            }
+            self.len += 8;
+        }
+        self.len -= len;
+        let bits = match self.order {
+            BitOrder::MsbFirst => {
+                let bits = self.bits >> self.len;
+                self.bits &= mask(self.len);
+                bits
+            }
+            BitOrder::LsbFirst => {
+                let bits = self.bits & mask(len);
+                self.bits >>= len;
+                bits
+            }
+        };
+        T::decode_bits(bits as u64, len)
+    }
+
+    /// Ends the run of bit fields.
+    ///
+    /// Fails with [UnalignedBits](error::ErrorKind::UnalignedBits) error, If it doesn't end on a byte boundary.
+    pub fn finish(self) -> Result<()> {
+        match self.len {
+            0 => Ok(()),
+            remaining => Err(Error::new(error::ErrorKind::UnalignedBits { remaining })),
+        }
+    }
+}
+
+impl<T: EncodeBits + ?Sized> EncodeBits for &T {
+    #[inline]
+    fn encode_bits(&self, len: u32) -> Option<u64> {
+        T::encode_bits(self, len)
+    }
+}
+
+impl EncodeBits for bool {
+    #[inline]
+    fn encode_bits(&self, _: u32) -> Option<u64> {
+        Some(*self as u64)
+    }
+}
+
+impl DecodeBits for bool {
+    #[inline]
+    fn decode_bits(bits: u64, _: u32) -> Result<Self> {
+        match bits {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(Error::new(error::ErrorKind::InvalidBoolValue(bits as u8))),
+        }
+    }
+}
+
+macro_rules! impl_bits_for {
+    [unsigned => $($ty:ty)*] => ($(
+        impl EncodeBits for $ty {
+            #[inline]
+            fn encode_bits(&self, len: u32) -> Option<u64> {
+                let num = u64::try_from(*self).ok()?;
+                (len >= u64::BITS || num >> len == 0).then_some(num)
+            }
+        }
+        impl DecodeBits for $ty {
+            #[inline]
+            fn decode_bits(bits: u64, _: u32) -> Result<Self> {
+                Ok(Self::try_from(bits)?)
+            }
+        }
+    )*);
+    [signed => $($ty:ty)*] => ($(
+        impl EncodeBits for $ty {
+            #[inline]
+            fn encode_bits(&self, len: u32) -> Option<u64> {
+                let num = i64::try_from(*self).ok()?;
+                let shift = u64::BITS - len;
+                ((num << shift) >> shift == num).then_some(num as u64 & mask(len) as u64)
+            }
+        }
+        impl DecodeBits for $ty {
+            #[inline]
+            fn decode_bits(bits: u64, len: u32) -> Result<Self> {
+                let shift = u64::BITS - len;
+                Ok(Self::try_from((bits << shift) as i64 >> shift)?)
+            }
+        }
+    )*);
+}
+
+impl_bits_for!(unsigned => u8 u16 u32 u64 usize);
+impl_bits_for!(signed => i8 i16 i32 i64 isize);
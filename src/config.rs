@@ -4,11 +4,14 @@
 ///
 /// - Numbers are represented in little endian byte order
 /// - Length of the collection is encoded with [crate::var_int::BEU30]
-pub const DEFAULT: u16 = num::LE | len::BEU30;
+/// - Signed numbers are [int_codec::ZIG_ZAG] encoded, With variable-length number encodings
+/// - `char` is encoded as `u32`, See [char_codec::UTF32]
+pub const DEFAULT: u16 = num::LE | len::BEU30 | int_codec::ZIG_ZAG | char_codec::UTF32;
 
 /// Configuration options for number representation
 pub mod num {
-    pub(crate) const GET: u16 = 0b1111;
+    #[doc(hidden)]
+    pub const GET: u16 = 0b1111;
 
     // Fixed size number encoding algorithm
 
@@ -21,26 +24,172 @@ pub mod num {
 
     /// [LEB128](https://en.wikipedia.org/wiki/LEB128) or Little Endian Base 128 is a variable-length code
     /// compression used to store arbitrarily large integers in a small number of bytes.
+    ///
+    /// Signed numbers are encoded with [int_codec](super::int_codec), Floats are represented with little endian byte order, Unless set with [float](super::float).
     pub const LEB128: u16 = 3;
 
-    // TODO: Not implemented yet...
-    // See: https://www.rfc-editor.org/rfc/rfc9000.html#name-variable-length-integer-enc
-    // pub const BEU62: u16 = 4;
+    /// Represents numbers with [crate::var_int::BEU62], The variable-length integer encoding of QUIC.
+    ///
+    /// Signed numbers are encoded with [int_codec](super::int_codec)
+    /// Floats are represented with big endian byte order, Unless set with [float](super::float).
+    /// Values above `2^62 - 1` fail with [crate::error::IntegerOverflow].
+    ///
+    /// See: <https://www.rfc-editor.org/rfc/rfc9000.html#name-variable-length-integer-enc>
+    pub const BEU62: u16 = 4;
 
-    // See: https://en.wikipedia.org/wiki/Variable-length_quantity
-    // pub const VLQ: u16 = 5;
+    /// Represents numbers with [crate::var_int::VLQ], The big-endian base-128 encoding of MIDI and Git.
+    ///
+    /// Signed numbers are encoded with [int_codec](super::int_codec), Floats are represented with big endian byte order, Unless set with [float](super::float).
+    ///
+    /// See: <https://en.wikipedia.org/wiki/Variable-length_quantity>
+    pub const VLQ: u16 = 5;
+
+    /// Represents numbers with [crate::var_int::CompactSize], The variable-length integer encoding of Bitcoin.
+    ///
+    /// Signed numbers are encoded with [int_codec](super::int_codec), Floats are represented with little endian byte order, Unless set with [float](super::float).
+    pub const COMPACT_SIZE: u16 = 6;
+
+    /// Represents numbers with [crate::var_int::SqliteVarint], The variable-length integer encoding of SQLite.
+    ///
+    /// Signed numbers are encoded with [int_codec](super::int_codec), Floats are represented with big endian byte order, Unless set with [float](super::float).
+    ///
+    /// See: <https://www.sqlite.org/fileformat2.html#varint>
+    pub const SQLITE_VARINT: u16 = 7;
+
+    /// Represents numbers with [crate::var_int::PrefixVarint], Length is prefixed in unary in the first byte.
+    ///
+    /// Signed numbers are encoded with [int_codec](super::int_codec), Floats are represented with little endian byte order, Unless set with [float](super::float).
+    pub const PREFIX_VARINT: u16 = 8;
 }
 
-// Negative Number encoding strategy, Used with variable integer encoding algorithms.
-// pub mod int_codec {
-//     pub const ZIG_ZAG: u16 = ...;
-//     // it use sign bit to represent negetive number.
-//     pub const SIGN_BIT: u16 = ...;
-// }
+/// Encoding of signed integers, Used with variable-length [num] options.
+///
+/// Fixed size [num] options ([num::LE], [num::BE], [num::NE]) ignore it.
+///
+/// ### Example
+///
+/// ```
+/// use databuf::{Encode, config::{num, int_codec}};
+///
+/// assert_eq!((-2_i32).to_bytes::<{ num::LEB128 | int_codec::ZIG_ZAG }>(), [3]);
+/// assert_eq!((-2_i32).to_bytes::<{ num::LEB128 | int_codec::SIGN_BIT }>(), [5]);
+/// assert_eq!((-2_i32).to_bytes::<{ num::LEB128 | int_codec::TWOS_COMPLEMENT }>(), [0x7E]);
+/// ```
+pub mod int_codec {
+    #[doc(hidden)]
+    pub const GET: u16 = 0b11_0000_0000;
+
+    /// (Default) [ZigZag](https://en.wikipedia.org/wiki/Variable-length_quantity#Zigzag_encoding) encoding,
+    /// Maps `0, -1, 1, -2, 2 ...` to `0, 1, 2, 3, 4 ...`
+    pub const ZIG_ZAG: u16 = 0 << 8;
+
+    /// Sign-magnitude encoding, The least significant bit is the sign bit and the rest is the magnitude.
+    ///
+    /// `i128::MIN` can't be encoded, Encoding fails with [crate::error::IntegerOverflow].
+    pub const SIGN_BIT: u16 = 1 << 8;
+
+    /// Two's complement encoding.
+    ///
+    /// With [num::LEB128](super::num::LEB128), It is signed LEB128, As used by DWARF and WebAssembly.
+    /// With other variable-length options, The number is reinterpreted as unsigned integer of the same width,
+    /// So `-1_i32` is encoded as `0xFFFF_FFFF`.
+    pub const TWOS_COMPLEMENT: u16 = 2 << 8;
+}
+
+/// Encoding of floats, Independent of the [num] option.
+///
+/// Options of different kinds can be combined, e.g. `float::BE | float::F16 | float::CANONICAL_NAN`.
+/// They are only supported with const `CONFIG`, Not with runtime [Config].
+///
+/// ### Example
+///
+/// ```
+/// use databuf::{Encode, config::{num, float}};
+///
+/// assert_eq!(1.5_f32.to_bytes::<{ num::LEB128 }>(), [0, 0, 0xC0, 0x3F]);
+/// assert_eq!(1.5_f32.to_bytes::<{ num::LEB128 | float::BE }>(), [0x3F, 0xC0, 0, 0]);
+/// assert_eq!(1.5_f32.to_bytes::<{ num::BE | float::F16 }>(), [0x3E, 0]);
+/// assert_eq!(1.5_f32.to_bytes::<{ num::BE | float::BF16 }>(), [0x3F, 0xC0]);
+/// assert_eq!((-f64::NAN).to_bytes::<{ num::BE | float::CANONICAL_NAN }>(), f64::NAN.to_be_bytes());
+/// ```
+pub mod float {
+    #[doc(hidden)]
+    pub const GET: u16 = super::float_order::GET | super::float_format::GET | super::float_nan::GET;
+
+    /// Floats are represented with little endian byte order, Whatever the [num](super::num) option is.
+    ///
+    /// By default, Floats follow the byte order of the [num](super::num) option.
+    pub const LE: u16 = super::float_order::LE;
+
+    /// Floats are represented with big endian byte order, Whatever the [num](super::num) option is.
+    pub const BE: u16 = super::float_order::BE;
+
+    /// `f32` is stored as IEEE 754 half-precision float (`f16`) in 2 bytes.
+    ///
+    /// Values are rounded to the nearest `f16`, Out of range values become infinity. `f64` is not affected.
+    pub const F16: u16 = super::float_format::F16;
+
+    /// `f32` is stored as [bfloat16](https://en.wikipedia.org/wiki/Bfloat16_floating-point_format) in 2 bytes,
+    /// It keeps the range of `f32` with less precision.
+    ///
+    /// Values are rounded to the nearest `bf16`. `f64` is not affected.
+    pub const BF16: u16 = super::float_format::BF16;
+
+    /// Every NaN is encoded as the same quiet NaN (`f32::NAN` / `f64::NAN`),
+    /// So equal values are always encoded into identical bytes.
+    pub const CANONICAL_NAN: u16 = super::float_nan::CANONICAL;
+}
+
+#[doc(hidden)]
+pub mod float_order {
+    pub const GET: u16 = 0b11 << 10;
+    pub const NUM: u16 = 0 << 10;
+    pub const LE: u16 = 1 << 10;
+    pub const BE: u16 = 2 << 10;
+}
+
+#[doc(hidden)]
+pub mod float_format {
+    pub const GET: u16 = 0b11 << 12;
+    pub const FULL: u16 = 0 << 12;
+    pub const F16: u16 = 1 << 12;
+    pub const BF16: u16 = 2 << 12;
+}
+
+#[doc(hidden)]
+pub mod float_nan {
+    pub const GET: u16 = 1 << 14;
+    pub const PRESERVE: u16 = 0 << 14;
+    pub const CANONICAL: u16 = 1 << 14;
+}
+
+/// Encoding of [char].
+///
+/// ### Example
+///
+/// ```
+/// use databuf::{Encode, config::{num, char_codec}};
+///
+/// assert_eq!('é'.to_bytes::<{ num::LE }>(), [0xE9, 0, 0, 0]);
+/// assert_eq!('é'.to_bytes::<{ num::LE | char_codec::UTF8 }>(), [0xC3, 0xA9]);
+/// ```
+pub mod char_codec {
+    #[doc(hidden)]
+    pub const GET: u16 = 1 << 15;
+
+    /// (Default) `char` is encoded as `u32` with the [num](super::num) option.
+    pub const UTF32: u16 = 0 << 15;
+
+    /// `char` is encoded as its 1 to 4 bytes UTF-8 sequence, Whatever the [num](super::num) option is.
+    ///
+    /// Malformed, Overlong and surrogate sequences fail with [crate::error::ErrorKind::InvalidChar].
+    pub const UTF8: u16 = 1 << 15;
+}
 
 /// Configuration options for representing length of collection.
 pub mod len {
-    pub(crate) const GET: u16 = 0b_111_0000;
+    #[doc(hidden)]
+    pub const GET: u16 = 0b_1111_0000;
 
     /// length is represented with [crate::var_int::BEU30] big-endian unsigned 30-bit integer.
     pub const BEU30: u16 = 0 << 4;
@@ -53,4 +202,198 @@ pub mod len {
 
     /// length is represented with [crate::var_int::BEU15] big-endian unsigned 15-bit integer.
     pub const BEU15: u16 = 3 << 4;
+
+    /// length is represented with [crate::var_int::BEU62] big-endian unsigned 62-bit integer.
+    pub const BEU62: u16 = 4 << 4;
+
+    /// length is represented with [crate::var_int::VLQ] big-endian base-128 integer.
+    pub const VLQ: u16 = 5 << 4;
+
+    /// length is represented with [crate::var_int::CompactSize] integer of Bitcoin.
+    pub const COMPACT_SIZE: u16 = 6 << 4;
+
+    /// length is represented with [crate::var_int::SqliteVarint] integer of SQLite.
+    pub const SQLITE_VARINT: u16 = 7 << 4;
+
+    /// length is represented with [crate::var_int::PrefixVarint] integer, Length is prefixed in unary.
+    pub const PREFIX_VARINT: u16 = 8 << 4;
+
+    /// length is represented with fixed-width `u8`.
+    ///
+    /// Fixed-width lengths are in the byte order of [num](super::num) option,
+    /// Variable-length [num](super::num) options use little endian byte order.
+    /// Longer collections fail with [crate::error::IntegerOverflow].
+    pub const U8: u16 = 9 << 4;
+
+    /// length is represented with fixed-width `u16`, See [U8].
+    pub const U16: u16 = 10 << 4;
+
+    /// length is represented with fixed-width `u32`, See [U8].
+    pub const U32: u16 = 11 << 4;
+
+    /// length is represented with fixed-width `u64` (Like bincode), See [U8].
+    pub const U64: u16 = 12 << 4;
+
+    /// length is represented with unsigned [LEB128](super::num::LEB128).
+    pub const LEB128: u16 = 13 << 4;
+}
+
+/// Configuration selected at runtime, e.g. negotiated at handshake time.
+///
+/// Methods like [Encode::encode_with](crate::Encode::encode_with) dispatch it to the const `CONFIG` code path,
+/// So the output is identical to using [num] and [len] options directly.
+///
+/// Each call of these methods instantiates the const `CONFIG` code path for every combination of [num] and [len] options,
+/// That is 126 copies of the encoder or decoder of the value. Other options ([int_codec], [char_codec] and [float])
+/// would multiply it further, So they are only supported with const `CONFIG`.
+///
+/// ### Example
+///
+/// ```
+/// use databuf::{Encode, Decode, config::{Config, num, len}};
+///
+/// let config = Config::new(num::BE | len::BEU15).unwrap();
+/// let bytes = (42_u16, "Hi").to_bytes_with(config);
+/// assert_eq!(bytes, (42_u16, "Hi").to_bytes::<{ num::BE | len::BEU15 }>());
+/// assert_eq!(<(u16, &str)>::from_bytes_with(config, &bytes).unwrap(), (42, "Hi"));
+///
+/// assert!(Config::new(0b1111).is_err());
+/// assert!(Config::new(num::BE | databuf::config::float::F16).is_err());
+/// assert!(Config::new(num::LEB128 | databuf::config::int_codec::SIGN_BIT).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Config(u16);
+
+impl Config {
+    /// Returns the configuration, If `bits` is a valid combination of [num] and [len] options.
+    pub const fn new(bits: u16) -> Result<Self, crate::error::InvalidConfig> {
+        if bits & !(num::GET | len::GET) == 0
+            && bits & num::GET <= num::PREFIX_VARINT
+            && bits & len::GET <= len::LEB128
+        {
+            Ok(Self(bits))
+        } else {
+            Err(crate::error::InvalidConfig { bits })
+        }
+    }
+
+    /// Returns the underlying bits.
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self(DEFAULT)
+    }
+}
+
+impl TryFrom<u16> for Config {
+    type Error = crate::error::InvalidConfig;
+    fn try_from(bits: u16) -> Result<Self, Self::Error> {
+        Self::new(bits)
+    }
+}
+
+/// Evaluates `$body` with `const $C: u16` set to the bits of `$config`.
+///
+/// Only [num] and [len] options are dispatched, See [Config].
+macro_rules! dispatch {
+    ($config:expr, $C:ident => $body:expr) => {
+        $crate::config::dispatch!(@next $config.bits(), $C => $body; 0; (num len))
+    };
+    (@next $bits:expr, $C:ident => $body:expr; $acc:expr; ()) => {{
+        const $C: u16 = $acc;
+        $body
+    }};
+    (@next $bits:expr, $C:ident => $body:expr; $acc:expr; ($opt:ident $($rest:ident)*)) => {
+        $crate::config::dispatch!(@$opt $bits, $C => $body; $acc; ($($rest)*))
+    };
+    (@num $($args:tt)*) => {
+        $crate::config::dispatch!(@match num $($args)*; LE BE NE LEB128 BEU62 VLQ COMPACT_SIZE SQLITE_VARINT PREFIX_VARINT)
+    };
+    (@len $($args:tt)*) => {
+        $crate::config::dispatch!(@match len $($args)*; BEU30 BEU29 BEU22 BEU15 BEU62 VLQ COMPACT_SIZE SQLITE_VARINT PREFIX_VARINT U8 U16 U32 U64 LEB128)
+    };
+    (@match $opt:ident $bits:expr, $C:ident => $body:expr; $acc:expr; $rest:tt; $($name:ident)*) => {
+        match $bits & $crate::config::$opt::GET {
+            $($crate::config::$opt::$name => {
+                $crate::config::dispatch!(@next $bits, $C => $body; $acc | $crate::config::$opt::$name; $rest)
+            })*
+            _ => unreachable!(),
+        }
+    };
+}
+pub(crate) use dispatch;
+
+/// Functions generic over `CONFIG`, Derive macros call them with the config of fields with pinned options.
+///
+/// The config is selected by a helper function per set of pinned options,
+/// So the options are dispatched once per type, Not once per field.
+#[doc(hidden)]
+pub mod visit {
+    use crate::{
+        io::{self, Read, Write},
+        limits::Budget,
+        Decode, DecodeFromReader, Encode, Result,
+    };
+    use core::marker::PhantomData;
+
+    pub trait Visit {
+        type Output;
+        fn visit<const CONFIG: u16>(self) -> Self::Output;
+    }
+
+    /// [Encode::encode] of a field.
+    pub struct Encoder<'a, T: ?Sized, W: ?Sized>(pub &'a T, pub &'a mut W);
+
+    impl<T: Encode + ?Sized, W: Write + ?Sized> Visit for Encoder<'_, T, W> {
+        type Output = io::Result<()>;
+        #[inline]
+        fn visit<const CONFIG: u16>(self) -> Self::Output {
+            self.0.encode::<CONFIG>(self.1)
+        }
+    }
+
+    /// [Encode::encoded_len] of a field.
+    pub struct EncodedLen<'a, T: ?Sized>(pub &'a T);
+
+    impl<T: Encode + ?Sized> Visit for EncodedLen<'_, T> {
+        type Output = usize;
+        #[inline]
+        fn visit<const CONFIG: u16>(self) -> Self::Output {
+            self.0.encoded_len::<CONFIG>()
+        }
+    }
+
+    /// [Decode::decode_limited] of a field.
+    pub struct Decoder<'a, 'de, T>(
+        pub &'a mut &'de [u8],
+        pub &'a mut Budget,
+        pub PhantomData<T>,
+    );
+
+    impl<'de, T: Decode<'de>> Visit for Decoder<'_, 'de, T> {
+        type Output = Result<T>;
+        #[inline]
+        fn visit<const CONFIG: u16>(self) -> Self::Output {
+            T::decode_limited::<CONFIG>(self.0, self.1)
+        }
+    }
+
+    /// [DecodeFromReader::decode_from_reader_limited] of a field.
+    pub struct ReaderDecoder<'a, T, R: ?Sized>(
+        pub &'a mut R,
+        pub &'a mut Budget,
+        pub PhantomData<T>,
+    );
+
+    impl<T: DecodeFromReader, R: Read + ?Sized> Visit for ReaderDecoder<'_, T, R> {
+        type Output = Result<T>;
+        #[inline]
+        fn visit<const CONFIG: u16>(self) -> Self::Output {
+            T::decode_from_reader_limited::<CONFIG>(self.0, self.1)
+        }
+    }
 }
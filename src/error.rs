@@ -1,9 +1,367 @@
-use std::error::Error;
-use std::fmt::{self, Display};
+use crate::io;
+use alloc::{boxed::Box, vec::Vec};
+use core::error::Error;
+use core::fmt::{self, Display};
+
+/// The error type returned by [Decode](crate::Decode).
+///
+/// Use [DecodeError::kind] to `match` on the cause of the failure.
+///
+/// ### Example
+///
+/// ```
+/// use databuf::{Decode, config::num::LE, error::ErrorKind};
+///
+/// let err = <(u8, bool)>::from_bytes::<LE>(&[1, 2]).unwrap_err();
+/// assert!(matches!(err.kind(), ErrorKind::InvalidBoolValue(2)));
+/// assert_eq!(err.offset(), Some(2));
+///
+/// let err = u32::from_bytes::<LE>(&[1, 2]).unwrap_err();
+/// assert!(matches!(err.kind(), ErrorKind::InsufficientBytes { missing: Some(2) }));
+/// assert_eq!(err.offset(), Some(0));
+/// ```
+pub struct DecodeError(Box<Inner>);
+
+struct Inner {
+    kind: ErrorKind,
+    offset: Option<usize>,
+    root: Option<&'static str>,
+    /// Innermost segment first.
+    path: Vec<PathSegment>,
+}
+
+/// A segment of the [Path] to the value, that failed to decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathSegment {
+    /// Named or tuple field, e.g. `.name` or `.0`
+    Field(&'static str),
+    /// `enum` variant, e.g. `::Ref`
+    Variant(&'static str),
+    /// Element index of a collection, e.g. `[3]`
+    Index(usize),
+}
+
+/// Breadcrumb path to the value, that failed to decode. e.g. `Company.cars[3].name`
+///
+/// It is displayed as the outermost derived type name, followed by each [PathSegment].
+#[derive(Debug, Clone, Copy)]
+pub struct Path<'a> {
+    root: Option<&'static str>,
+    path: &'a [PathSegment],
+}
+
+impl<'a> Path<'a> {
+    /// Name of the outermost derived type, If any.
+    pub fn root(&self) -> Option<&'static str> {
+        self.root
+    }
+
+    /// Iterate over the segments, Starting from the outermost.
+    pub fn segments(&self) -> impl DoubleEndedIterator<Item = &'a PathSegment> {
+        self.path.iter().rev()
+    }
+}
+
+impl Display for Path<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(root) = self.root {
+            f.write_str(root)?;
+        }
+        for segment in self.segments() {
+            match segment {
+                PathSegment::Field(name) => write!(f, ".{name}")?,
+                PathSegment::Variant(name) => write!(f, "::{name}")?,
+                PathSegment::Index(idx) => write!(f, "[{idx}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A list specifying the causes of [DecodeError].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// There are not enough bytes in the input to complete the decoding process.
+    InsufficientBytes {
+        /// Number of missing bytes.
+        ///
+        /// It is `None` when decoding from a [Read](crate::io::Read) source, As it doesn't report how many bytes were available.
+        missing: Option<usize>,
+    },
+    /// A [bool] value is expected, but the byte is not `0` or `1`.
+    InvalidBoolValue(u8),
+    /// The value is not a valid [char].
+    ///
+    /// With [char_codec::UTF8](crate::config::char_codec::UTF8), It is the bytes of the malformed sequence in big endian order.
+    InvalidChar(u32),
+    /// The integer value exceeds the maximum value that can be represented by the target integer type.
+    IntegerOverflow,
+    /// A variable-length integer is not encoded in the shortest form.
+    NonCanonical,
+    /// A run of bit fields doesn't end on a byte boundary, See [crate::bits].
+    UnalignedBits {
+        /// Number of bits left in the last byte.
+        remaining: u32,
+    },
+    /// A string contains invalid UTF-8.
+    InvalidUtf8 {
+        /// Length of the valid UTF-8 prefix of the string.
+        valid_up_to: usize,
+        /// The offending bytes.
+        bytes: Vec<u8>,
+    },
+    /// An `enum` has an unknown discriminant value.
+    UnknownDiscriminant {
+        /// Path of the `enum`
+        ident: &'static str,
+        /// Unrecognized discriminant value
+        discriminant: i128,
+    },
+    /// Input is left over after decoding, See [Decode::from_bytes_exact](crate::Decode::from_bytes_exact).
+    TrailingBytes {
+        /// Number of remaining bytes.
+        remaining: usize,
+    },
+    /// A [Limits](crate::Limits) was exceeded.
+    LimitExceeded(Limit),
+    /// An error returned by the [Read](crate::io::Read) source.
+    Io(io::Error),
+    /// Any other error, Returned by user defined [Decode](crate::Decode) implementations.
+    Custom(Box<dyn Error + Send + Sync>),
+}
+
+/// A list of [Limits](crate::Limits), that may be exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    /// [Limits::max_len](crate::Limits::max_len)
+    Len,
+    /// [Limits::max_str_len](crate::Limits::max_str_len)
+    StrLen,
+    /// [Limits::max_alloc](crate::Limits::max_alloc)
+    Alloc,
+    /// [Limits::max_depth](crate::Limits::max_depth)
+    Depth,
+    /// [Limits::max_frame](crate::Limits::max_frame)
+    Frame,
+}
+
+impl DecodeError {
+    /// Creates a new error from a known kind of error.
+    pub fn new(kind: ErrorKind) -> Self {
+        Self(Box::new(Inner {
+            kind,
+            offset: None,
+            root: None,
+            path: Vec::new(),
+        }))
+    }
+
+    /// Creates a new error from an arbitrary error payload.
+    pub fn custom(error: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        Self::new(ErrorKind::Custom(error.into()))
+    }
+
+    /// Returns the corresponding [ErrorKind] for this error.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.0.kind
+    }
+
+    /// Consumes the error, returning its [ErrorKind].
+    pub fn into_kind(self) -> ErrorKind {
+        self.0.kind
+    }
+
+    /// Number of bytes consumed from the input, before the error was detected.
+    ///
+    /// It is only known when decoding with [Decode::from_bytes](crate::Decode::from_bytes) and its variants.
+    pub fn offset(&self) -> Option<usize> {
+        self.0.offset
+    }
+
+    /// Path to the value that failed to decode, If it was decoded from a derived type or a collection.
+    pub fn path(&self) -> Option<Path<'_>> {
+        let Inner { root, path, .. } = &*self.0;
+        (root.is_some() || !path.is_empty()).then_some(Path { root: *root, path })
+    }
+
+    /// Adds `segments` (innermost first) to the path, and sets the outermost type name.
+    #[inline(never)]
+    #[doc(hidden)]
+    pub fn context(mut self, root: &'static str, segments: &[PathSegment]) -> Self {
+        self.0.path.extend_from_slice(segments);
+        self.0.root = Some(root);
+        self
+    }
+
+    #[inline(never)]
+    pub(crate) fn index(mut self, idx: usize) -> Self {
+        self.0.path.push(PathSegment::Index(idx));
+        self
+    }
+
+    pub(crate) fn at(mut self, offset: usize) -> Self {
+        self.0.offset.get_or_insert(offset);
+        self
+    }
+
+    pub(crate) fn insufficient_bytes(missing: usize) -> Self {
+        Self::new(ErrorKind::InsufficientBytes {
+            missing: Some(missing),
+        })
+    }
+
+    pub(crate) fn invalid_utf8(bytes: &[u8], error: core::str::Utf8Error) -> Self {
+        let bytes = &bytes[error.valid_up_to()..];
+        let len = error.error_len().unwrap_or(bytes.len());
+        Self::new(ErrorKind::InvalidUtf8 {
+            valid_up_to: error.valid_up_to(),
+            bytes: bytes[..len].to_vec(),
+        })
+    }
+
+    #[inline]
+    #[doc(hidden)]
+    pub fn unknown_discriminant<T>(ident: &'static str, discriminant: i128) -> crate::Result<T> {
+        Err(Self::new(ErrorKind::UnknownDiscriminant {
+            ident,
+            discriminant,
+        }))
+    }
+}
+
+impl fmt::Debug for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecodeError")
+            .field("kind", &self.0.kind)
+            .field("offset", &self.0.offset)
+            .field("path", &self.path())
+            .finish()
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0.kind, f)?;
+        if let Some(path) = self.path() {
+            write!(f, " in `{path}`")?;
+        }
+        if let Some(offset) = self.0.offset {
+            write!(f, " at byte offset {offset}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InsufficientBytes { missing: Some(n) } => {
+                write!(f, "insufficient bytes: {n} more required")
+            }
+            Self::InsufficientBytes { missing: None } => write!(f, "insufficient bytes"),
+            Self::InvalidBoolValue(byte) => write!(
+                f,
+                "invalid value for bool type: expected 0 or 1, found {byte}"
+            ),
+            Self::InvalidChar(num) => write!(f, "invalid char: {num:#x}"),
+            Self::IntegerOverflow => write!(f, "out of range integral type conversion attempted"),
+            Self::NonCanonical => write!(f, "non-canonical variable-length integer encoding"),
+            Self::UnalignedBits { remaining } => {
+                write!(
+                    f,
+                    "bit fields don't end on a byte boundary, {remaining} bits left"
+                )
+            }
+            Self::InvalidUtf8 { valid_up_to, bytes } => write!(
+                f,
+                "invalid utf-8 sequence {bytes:x?} after {valid_up_to} valid bytes"
+            ),
+            Self::UnknownDiscriminant {
+                ident,
+                discriminant,
+            } => write!(f, "unknown `{discriminant}` discriminator of `{ident}`"),
+            Self::TrailingBytes { remaining } => write!(f, "{remaining} trailing bytes left over"),
+            Self::LimitExceeded(limit) => {
+                let limit = match limit {
+                    Limit::Len => "collection length",
+                    Limit::StrLen => "string length",
+                    Limit::Alloc => "allocation",
+                    Limit::Depth => "nesting depth",
+                    Limit::Frame => "frame size",
+                };
+                write!(f, "{limit} limit exceeded")
+            }
+            Self::Io(err) => err.fmt(f),
+            Self::Custom(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.0.kind {
+            ErrorKind::Io(err) => Some(err),
+            ErrorKind::Custom(err) => Some(&**err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ErrorKind> for DecodeError {
+    fn from(kind: ErrorKind) -> Self {
+        Self::new(kind)
+    }
+}
+
+impl From<io::Error> for DecodeError {
+    fn from(err: io::Error) -> Self {
+        Self::new(ErrorKind::Io(err))
+    }
+}
+
+impl From<Box<dyn Error + Send + Sync>> for DecodeError {
+    fn from(err: Box<dyn Error + Send + Sync>) -> Self {
+        Self::new(ErrorKind::Custom(err))
+    }
+}
+
+impl From<InsufficientBytes> for DecodeError {
+    fn from(_: InsufficientBytes) -> Self {
+        Self::new(ErrorKind::InsufficientBytes { missing: None })
+    }
+}
+
+impl From<IntegerOverflow> for DecodeError {
+    fn from(_: IntegerOverflow) -> Self {
+        Self::new(ErrorKind::IntegerOverflow)
+    }
+}
+
+impl From<core::num::TryFromIntError> for DecodeError {
+    fn from(_: core::num::TryFromIntError) -> Self {
+        Self::new(ErrorKind::IntegerOverflow)
+    }
+}
+
+impl From<core::convert::Infallible> for DecodeError {
+    fn from(never: core::convert::Infallible) -> Self {
+        match never {}
+    }
+}
+
+impl From<alloc::string::FromUtf8Error> for DecodeError {
+    fn from(err: alloc::string::FromUtf8Error) -> Self {
+        Self::invalid_utf8(err.as_bytes(), err.utf8_error())
+    }
+}
 
 /// `enum` uses a discriminator to distinguish its variants.
 ///
 /// This `UnknownDiscriminant` can happen when decoding an `enum` type that has an unknown discriminator value.
+#[deprecated(
+    note = "decoders never return it, Match `ErrorKind::UnknownDiscriminant` of `DecodeError::kind` instead"
+)]
 #[derive(Debug)]
 pub struct UnknownDiscriminant<T> {
     /// Path of the `enum` struct
@@ -17,37 +375,44 @@ pub struct UnknownDiscriminant<T> {
 pub struct InsufficientBytes;
 
 /// Occurs when invalid utf8 character found during the decoding process.
+///
+/// Or when a character can't be encoded, See [Latin1](crate::text::Latin1).
 #[derive(Debug)]
 pub struct InvalidChar;
 
+/// Occurs when a [NulTerminated](crate::text::NulTerminated) string contains `'\0'` character.
+#[derive(Debug)]
+pub struct InteriorNul;
+
 /// Occurs when the integer value exceeds the maximum value that can be represented by the target integer type.
 #[derive(Debug)]
 pub struct IntegerOverflow;
 
+/// Occurs when the bits are not a valid [Config](crate::config::Config).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidConfig {
+    /// The invalid bits
+    pub bits: u16,
+}
+
 /// Occurs during decoding when a [bool] value is expected, but the byte contains a value that is not `0` or `1`.
+#[deprecated(
+    note = "decoders never return it, Match `ErrorKind::InvalidBoolValue` of `DecodeError::kind` instead"
+)]
 #[derive(Debug)]
 pub struct InvalidBoolValue;
 
-impl<T> Error for UnknownDiscriminant<T> where T: std::fmt::Debug + Display {}
+#[allow(deprecated)]
+impl<T> Error for UnknownDiscriminant<T> where T: core::fmt::Debug + Display {}
 impl Error for InsufficientBytes {}
 impl Error for InvalidChar {}
+impl Error for InteriorNul {}
 impl Error for IntegerOverflow {}
+#[allow(deprecated)]
 impl Error for InvalidBoolValue {}
+impl Error for InvalidConfig {}
 
-impl<D> UnknownDiscriminant<D>
-where
-    D: std::fmt::Debug + Display + Send + Sync + 'static,
-{
-    #[inline]
-    #[doc(hidden)]
-    pub fn new_boxed_err<T>(ident: &'static str, discriminant: D) -> crate::Result<T> {
-        Err(Box::new(Self {
-            ident,
-            discriminant,
-        }))
-    }
-}
-
+#[allow(deprecated)]
 impl<T: Display> Display for UnknownDiscriminant<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let Self {
@@ -68,11 +433,22 @@ impl Display for InvalidChar {
         writeln!(f, "invalid char")
     }
 }
+impl Display for InteriorNul {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "nul-terminated string contains nul character")
+    }
+}
 impl Display for IntegerOverflow {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "out of range integral type conversion attempted")
     }
 }
+impl Display for InvalidConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid config: {:#018b}", self.bits)
+    }
+}
+#[allow(deprecated)]
 impl Display for InvalidBoolValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "invalid value for bool type: expected 0 or 1")
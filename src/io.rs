@@ -0,0 +1,166 @@
+//! Traits, helpers, and type definitions for byte sinks and sources.
+//!
+//! With the `std` feature enabled, [Write] and [Read] are implemented for every [std::io::Write] and [std::io::Read] type,
+//! and [Error] is [std::io::Error].
+//! Otherwise they are implemented for `&mut [u8]`, [Vec<u8>] and `&[u8]`.
+
+#[cfg(feature = "std")]
+pub use std::io::{Error, ErrorKind, Result};
+
+#[cfg(not(feature = "std"))]
+pub use no_std::{Error, ErrorKind, Result};
+
+/// A byte sink, that [crate::Encode] writes to.
+pub trait Write {
+    /// Attempts to write an entire buffer into this writer.
+    fn write_all(&mut self, buf: &[u8]) -> Result<()>;
+}
+
+/// A byte source, that [crate::DecodeFromReader::decode_from_reader] reads from.
+pub trait Read {
+    /// Read the exact number of bytes required to fill `buf`.
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()>;
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write + ?Sized> Write for W {
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        std::io::Write::write_all(self, buf)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read + ?Sized> Read for R {
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        std::io::Read::read_exact(self, buf)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+mod no_std {
+    use super::*;
+    use alloc::{boxed::Box, vec::Vec};
+    use core::fmt;
+
+    /// A specialized [core::result::Result] type for I/O operations.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// A list specifying general categories of I/O error.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[non_exhaustive]
+    pub enum ErrorKind {
+        /// A parameter was incorrect.
+        InvalidInput,
+        /// An error returned when an operation could not be completed because a call to [Write::write_all] returned `0`.
+        WriteZero,
+        /// An error returned when an operation could not be completed because an "end of file" was reached prematurely.
+        UnexpectedEof,
+        /// This operation is unsupported.
+        Unsupported,
+        /// A custom error that does not fall under any other I/O error kind.
+        Other,
+    }
+
+    /// The error type for I/O operations of the [Write] and [Read] traits.
+    #[derive(Debug)]
+    pub struct Error {
+        kind: ErrorKind,
+        error: Option<Box<dyn core::error::Error + Send + Sync>>,
+    }
+
+    impl Error {
+        /// Creates a new I/O error from a known kind of error as well as an arbitrary error payload.
+        pub fn new<E>(kind: ErrorKind, error: E) -> Self
+        where
+            E: Into<Box<dyn core::error::Error + Send + Sync>>,
+        {
+            Self {
+                kind,
+                error: Some(error.into()),
+            }
+        }
+
+        /// Returns the corresponding [ErrorKind] for this error.
+        pub fn kind(&self) -> ErrorKind {
+            self.kind
+        }
+
+        /// Returns a reference to the inner error wrapped by this error (if any).
+        pub fn get_ref(&self) -> Option<&(dyn core::error::Error + Send + Sync + 'static)> {
+            self.error.as_deref()
+        }
+    }
+
+    impl From<ErrorKind> for Error {
+        fn from(kind: ErrorKind) -> Self {
+            Self { kind, error: None }
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match &self.error {
+                Some(error) => error.fmt(f),
+                None => fmt::Debug::fmt(&self.kind, f),
+            }
+        }
+    }
+
+    impl core::error::Error for Error {}
+
+    impl<W: Write + ?Sized> Write for &mut W {
+        #[inline]
+        fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+            (**self).write_all(buf)
+        }
+    }
+
+    impl Write for &mut [u8] {
+        #[inline]
+        fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+            if buf.len() > self.len() {
+                return Err(Error::new(
+                    ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                ));
+            }
+            let (a, b) = core::mem::take(self).split_at_mut(buf.len());
+            a.copy_from_slice(buf);
+            *self = b;
+            Ok(())
+        }
+    }
+
+    impl Write for Vec<u8> {
+        #[inline]
+        fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+            self.extend_from_slice(buf);
+            Ok(())
+        }
+    }
+
+    impl<R: Read + ?Sized> Read for &mut R {
+        #[inline]
+        fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+            (**self).read_exact(buf)
+        }
+    }
+
+    impl Read for &[u8] {
+        #[inline]
+        fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+            if buf.len() > self.len() {
+                return Err(Error::new(
+                    ErrorKind::UnexpectedEof,
+                    "failed to fill whole buffer",
+                ));
+            }
+            let (a, b) = self.split_at(buf.len());
+            buf.copy_from_slice(a);
+            *self = b;
+            Ok(())
+        }
+    }
+}
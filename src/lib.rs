@@ -1,12 +1,24 @@
 #![doc = include_str!("../README.md")]
 #![warn(missing_docs)]
+#![no_std]
 // #![cfg_attr(feature = "nightly", feature(min_specialization))]
 
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 pub use databuf_derive::*;
+#[cfg(feature = "async")]
+pub mod async_io;
+pub mod bits;
 /// contains configuration options.
 pub mod config;
 /// This module defines the error types.
 pub mod error;
+pub mod io;
+pub mod limits;
+pub mod size;
+pub mod text;
 /// This module provides types for encoding and decoding variable-length integers
 pub mod var_int;
 
@@ -14,21 +26,51 @@ mod record;
 mod types;
 mod utils;
 
-use std::{io, io::Write};
+use alloc::{boxed::Box, string::String, vec::Vec};
+use config::Config;
+use io::{Read, Write};
+use limits::Budget;
+pub use limits::Limits;
+pub use size::MaxEncodedSize;
 
-/// It is an alias for a boxed [std::error::Error].
-pub type Error = Box<dyn std::error::Error + Send + Sync>;
+/// It is an alias for [error::DecodeError].
+pub type Error = error::DecodeError;
 
 /// It is an alias for a `Result<T, Error>` type.
 ///
-/// `Error` is an alias for boxed [std::error::Error] that may occur during [Decode::decode] operation.
-pub type Result<T, E = Error> = std::result::Result<T, E>;
+/// `Error` is an alias for [error::DecodeError] that may occur during [Decode::decode] operation.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
 
 /// This trait used to serialize the data structure into binary format.
 pub trait Encode {
     /// Serialize the data into binary format.
     fn encode<const CONFIG: u16>(&self, _: &mut (impl Write + ?Sized)) -> io::Result<()>;
 
+    /// Returns the exact number of bytes, that [Encode::encode] writes.
+    ///
+    /// The default implementation counts the bytes by encoding the value.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use databuf::{Encode, config::num::{LE, LEB128}};
+    ///
+    /// #[derive(Encode)]
+    /// struct FooBar {
+    ///     foo: u32,
+    ///     bar: String,
+    /// }
+    /// let foobar = FooBar { foo: 42, bar: "Hello".into() };
+    /// assert_eq!(foobar.encoded_len::<LE>(), 4 + 1 + 5);
+    /// assert_eq!(foobar.encoded_len::<LEB128>(), 1 + 1 + 5);
+    /// ```
+    #[inline]
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        let mut counter = utils::Counter(0);
+        let _ = self.encode::<CONFIG>(&mut counter);
+        counter.0
+    }
+
     /// This is a convenient method used to encode a value into binary data and return it as a [Vec<u8>].
     ///
     /// ### Example
@@ -46,17 +88,88 @@ pub trait Encode {
     /// ```
     #[inline]
     fn to_bytes<const CONFIG: u16>(&self) -> Vec<u8> {
-        let mut vec = Vec::new();
+        let mut vec = Vec::with_capacity(self.encoded_len::<CONFIG>());
         self.encode::<CONFIG>(&mut vec).unwrap();
         vec
     }
+
+    /// Encode the value into a stack allocated `[u8; N]` array, Returns the array and the number of bytes written.
+    ///
+    /// It fails to compile, If [MaxEncodedSize::MAX_ENCODED_SIZE] is unbounded or greater than `N`.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use databuf::{Encode, config::num::LE};
+    ///
+    /// #[derive(Encode)]
+    /// struct FooBar {
+    ///     foo: u8,
+    ///     bar: [u16; 2],
+    /// }
+    /// let (bytes, len) = FooBar { foo: 1, bar: [2, 3] }.to_array::<LE, 5>();
+    /// assert_eq!(len, 5);
+    /// assert_eq!(bytes, [1, 2, 0, 3, 0]);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use databuf::{Encode, config::num::LE};
+    ///
+    /// let (bytes, len) = vec![1_u8, 2, 3].to_array::<LE, 16>();
+    /// ```
+    #[inline]
+    fn to_array<const CONFIG: u16, const N: usize>(&self) -> ([u8; N], usize)
+    where
+        Self: MaxEncodedSize<CONFIG>,
+    {
+        const {
+            assert!(
+                matches!(<Self as MaxEncodedSize<CONFIG>>::MAX_ENCODED_SIZE, Some(size) if size <= N),
+                "`MAX_ENCODED_SIZE` is unbounded or exceeds the array length"
+            )
+        };
+        let mut buf = [0; N];
+        let mut writer = buf.as_mut_slice();
+        self.encode::<CONFIG>(&mut writer).unwrap();
+        let len = N - writer.len();
+        (buf, len)
+    }
+
+    /// Same as [Encode::encode], But with runtime [Config].
+    ///
+    /// It instantiates [Encode::encode] for each of the 126 combinations of [num](config::num) and [len](config::len) options,
+    /// So every type encoded this way adds 126 copies of its encoder to the binary and to compile time.
+    /// Prefer const `CONFIG`, When the config is known at compile time.
+    #[inline]
+    fn encode_with(&self, config: Config, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
+        config::dispatch!(config, C => self.encode::<C>(c))
+    }
+
+    /// Same as [Encode::to_bytes], But with runtime [Config].
+    ///
+    /// It calls [Encode::encode_with], So it shares the 126 copies of the encoder, See its compile cost.
+    #[inline]
+    fn to_bytes_with(&self, config: Config) -> Vec<u8> {
+        let mut vec = Vec::new();
+        self.encode_with(config, &mut vec).unwrap();
+        vec
+    }
 }
 
 /// This trait used to deserialize the data structure from binary format.
 pub trait Decode<'de>: Sized {
-    /// Deserialize the data from binary format.
+    /// Deserialize the data from binary format, Without any [Limits].
     fn decode<const CONFIG: u16>(_: &mut &'de [u8]) -> Result<Self>;
 
+    /// Deserialize the data from binary format, Within the `budget` of [Limits].
+    ///
+    /// Nested values are decoded with the same `budget`. By default it calls [Decode::decode] and ignores the `budget`,
+    /// Types that allocate or contain other values override it and [Decode::decode] decodes with [Budget::unlimited].
+    #[inline]
+    fn decode_limited<const CONFIG: u16>(c: &mut &'de [u8], _: &mut Budget) -> Result<Self> {
+        Self::decode::<CONFIG>(c)
+    }
+
     /// This is a convenient method used to decode a value from slice.
     ///
     /// ### Example
@@ -75,8 +188,84 @@ pub trait Decode<'de>: Sized {
     /// ```
     #[inline]
     fn from_bytes<const CONFIG: u16>(bytes: &'de [u8]) -> Result<Self> {
+        Self::from_bytes_with_rest::<CONFIG>(bytes).map(|(value, _)| value)
+    }
+
+    /// Same as [Decode::from_bytes], But fails with [TrailingBytes](error::ErrorKind::TrailingBytes) error,
+    /// If any bytes are left over after decoding.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use databuf::{Decode, config::num::LE, error::ErrorKind};
+    ///
+    /// assert_eq!(u16::from_bytes_exact::<LE>(&[1, 0]).unwrap(), 1);
+    ///
+    /// let err = u16::from_bytes_exact::<LE>(&[1, 0, 2]).unwrap_err();
+    /// assert!(matches!(err.kind(), ErrorKind::TrailingBytes { remaining: 1 }));
+    /// ```
+    #[inline]
+    fn from_bytes_exact<const CONFIG: u16>(bytes: &'de [u8]) -> Result<Self> {
+        let (value, rest) = Self::from_bytes_with_rest::<CONFIG>(bytes)?;
+        if !rest.is_empty() {
+            let err = Error::new(error::ErrorKind::TrailingBytes {
+                remaining: rest.len(),
+            });
+            return Err(err.at(bytes.len() - rest.len()));
+        }
+        Ok(value)
+    }
+
+    /// Decode a value from the start of `bytes`, Returns the value and the remaining bytes.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use databuf::{Decode, config::num::LE};
+    ///
+    /// let (num, rest) = u16::from_bytes_with_rest::<LE>(&[1, 0, 2, 3]).unwrap();
+    /// assert_eq!(num, 1);
+    /// assert_eq!(rest, [2, 3]);
+    /// ```
+    #[inline]
+    fn from_bytes_with_rest<const CONFIG: u16>(bytes: &'de [u8]) -> Result<(Self, &'de [u8])> {
+        let mut reader = bytes;
+        match Decode::decode::<CONFIG>(&mut reader) {
+            Ok(value) => Ok((value, reader)),
+            Err(err) => Err(err.at(bytes.len() - reader.len())),
+        }
+    }
+
+    /// Same as [Decode::from_bytes], But fails if any of the [Limits] is exceeded.
+    ///
+    /// See [Decode::decode_limited] to share a [Budget] between decode operations.
+    #[inline]
+    fn from_bytes_with_limits<const CONFIG: u16>(
+        bytes: &'de [u8],
+        limits: &Limits,
+    ) -> Result<Self> {
+        let mut reader = bytes;
+        Self::decode_limited::<CONFIG>(&mut reader, &mut Budget::new(*limits))
+            .map_err(|err| err.at(bytes.len() - reader.len()))
+    }
+
+    /// Same as [Decode::decode], But with runtime [Config].
+    ///
+    /// It instantiates [Decode::decode] for each of the 126 combinations of [num](config::num) and [len](config::len) options,
+    /// So every type decoded this way adds 126 copies of its decoder to the binary and to compile time.
+    /// Prefer const `CONFIG`, When the config is known at compile time.
+    #[inline]
+    fn decode_with(config: Config, c: &mut &'de [u8]) -> Result<Self> {
+        config::dispatch!(config, C => Self::decode::<C>(c))
+    }
+
+    /// Same as [Decode::from_bytes], But with runtime [Config].
+    ///
+    /// It calls [Decode::decode_with], So it shares the 126 copies of the decoder, See its compile cost.
+    #[inline]
+    fn from_bytes_with(config: Config, bytes: &'de [u8]) -> Result<Self> {
         let mut reader = bytes;
-        Decode::decode::<CONFIG>(&mut reader)
+        Self::decode_with(config, &mut reader).map_err(|err| err.at(bytes.len() - reader.len()))
     }
 }
 
@@ -85,3 +274,58 @@ pub trait Decode<'de>: Sized {
 /// This trait is automatically implemented for any type that implements the [Decode] trait.
 pub trait DecodeOwned: for<'de> Decode<'de> {}
 impl<T> DecodeOwned for T where T: for<'de> Decode<'de> {}
+
+/// This trait used to deserialize owned value directly from any [Read] source.
+///
+/// Borrowed types (such as `&str`, `&[u8]`) can't be decoded from reader, So they don't implement this trait.
+/// `#[derive(Decode)]` implements it, When every field can be decoded from reader.
+pub trait DecodeFromReader: Sized {
+    /// Deserialize the value from `reader`, Within the `budget` of [Limits].
+    ///
+    /// Nested values are decoded with the same `budget`.
+    fn decode_from_reader_limited<const CONFIG: u16>(
+        reader: &mut (impl Read + ?Sized),
+        budget: &mut Budget,
+    ) -> Result<Self>;
+
+    /// Deserialize the value from `reader`, Without any [Limits].
+    ///
+    /// Only the bytes required to decode the value are read from `reader`.
+    ///
+    /// ### Example
+    ///
+    /// ```
+    /// use databuf::{Decode, DecodeFromReader, config::num::LE};
+    ///
+    /// #[derive(Decode, PartialEq, Debug)]
+    /// struct FooBar {
+    ///     foo: u8,
+    ///     bar: [u8; 2],
+    /// }
+    ///
+    /// let mut reader: &[u8] = &[1, 2, 3, 4];
+    /// let foobar = FooBar::decode_from_reader::<LE>(&mut reader).unwrap();
+    /// assert_eq!(foobar, FooBar { foo: 1, bar: [2, 3] });
+    /// assert_eq!(reader, [4]);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use databuf::{DecodeFromReader, config::num::LE};
+    ///
+    /// let mut reader: &[u8] = &[5, b'H', b'e', b'l', b'l', b'o'];
+    /// let text = <&str>::decode_from_reader::<LE>(&mut reader);
+    /// ```
+    #[inline]
+    fn decode_from_reader<const CONFIG: u16>(reader: &mut (impl Read + ?Sized)) -> Result<Self> {
+        Self::decode_from_reader_limited::<CONFIG>(reader, &mut Budget::unlimited())
+    }
+
+    /// Same as [DecodeFromReader::decode_from_reader], But fails if any of the [Limits] is exceeded.
+    #[inline]
+    fn decode_from_reader_with_limits<const CONFIG: u16>(
+        reader: &mut (impl Read + ?Sized),
+        limits: &Limits,
+    ) -> Result<Self> {
+        Self::decode_from_reader_limited::<CONFIG>(reader, &mut Budget::new(*limits))
+    }
+}
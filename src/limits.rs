@@ -0,0 +1,163 @@
+//! Limits for decoding untrusted input.
+//!
+//! Length prefixes are read from the input, So a few bytes can claim a huge collection.
+//! [Limits] bounds the collection length, string length, total allocated bytes and nesting depth of derived types.
+//! Violations fail with [ErrorKind::LimitExceeded].
+//!
+//! Limits are tracked by a [Budget], That is passed to every nested decoder, So they also apply in `no_std` and async code.
+//! Manual impls that only implement [Decode::decode](crate::Decode::decode) decode their nested values without limits,
+//! Override [Decode::decode_limited](crate::Decode::decode_limited) to pass the `budget` to them.
+//! Custom decoders of `#[databuf(with = "...")]` and `#[databuf(decode_with = "...")]` fields don't receive the `budget`,
+//! So their values are unbounded, Only the nesting depth of the type that contains them is checked.
+//! Regardless of limits, decoders never preallocate more than a few KiB ahead of the decoded elements.
+//!
+//! ### Example
+//!
+//! ```
+//! use databuf::{*, limits::Budget, config::num::LE, error::{ErrorKind, Limit}};
+//!
+//! let limits = Limits { max_len: 16, ..Limits::default() };
+//!
+//! let err = Vec::<u8>::from_bytes_with_limits::<LE>(&[0x7F, 0xFF, 0xFF, 0xFF], &limits).unwrap_err();
+//! assert!(matches!(err.kind(), ErrorKind::LimitExceeded(Limit::Len)));
+//!
+//! let mut reader: &[u8] = &[2, 1, 2];
+//! let bytes = Vec::<u8>::decode_from_reader_limited::<LE>(&mut reader, &mut Budget::new(limits)).unwrap();
+//! assert_eq!(bytes, [1, 2]);
+//! ```
+
+use crate::{
+    error::{ErrorKind, Limit},
+    Error, Result,
+};
+use core::ops::{Deref, DerefMut};
+
+/// Maximum number of bytes, that collection decoders reserve ahead of decoded elements.
+pub(crate) const MAX_PREALLOC: usize = 4 * 1024;
+
+/// Decode limits, Every field defaults to unlimited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Maximum number of elements in a collection.
+    pub max_len: usize,
+    /// Maximum length of a string in bytes.
+    pub max_str_len: usize,
+    /// Maximum number of bytes allocated for collections and strings, in total.
+    pub max_alloc: usize,
+    /// Maximum nesting depth of derived types.
+    pub max_depth: usize,
+    /// Maximum number of bytes, that `AsyncDecoder` buffers for a single value.
+    pub max_frame: usize,
+}
+
+impl Limits {
+    /// No limits at all.
+    pub const UNLIMITED: Self = Self {
+        max_len: usize::MAX,
+        max_str_len: usize::MAX,
+        max_alloc: usize::MAX,
+        max_depth: usize::MAX,
+        max_frame: usize::MAX,
+    };
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
+
+/// What is left of the [Limits] during a decode operation.
+///
+/// It is passed to every nested decoder, See [Decode::decode_limited](crate::Decode::decode_limited).
+#[derive(Debug, Clone)]
+pub struct Budget {
+    limits: Limits,
+    alloc: usize,
+    depth: usize,
+}
+
+impl Budget {
+    /// Budget of a new decode operation.
+    pub const fn new(limits: Limits) -> Self {
+        Self {
+            limits,
+            alloc: 0,
+            depth: 0,
+        }
+    }
+
+    /// Budget without any limits, Used by [Decode::decode](crate::Decode::decode).
+    pub const fn unlimited() -> Self {
+        Self::new(Limits::UNLIMITED)
+    }
+
+    /// Limits of this decode operation.
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    #[inline]
+    fn check(ok: bool, limit: Limit) -> Result<()> {
+        if ok {
+            Ok(())
+        } else {
+            Err(Error::new(ErrorKind::LimitExceeded(limit)))
+        }
+    }
+
+    #[inline]
+    fn alloc(&mut self, bytes: usize) -> Result<()> {
+        self.alloc = self.alloc.saturating_add(bytes);
+        Self::check(self.alloc <= self.limits.max_alloc, Limit::Alloc)
+    }
+
+    /// Checks a collection of `len` elements of type `T`, Before it is decoded.
+    #[inline]
+    pub fn collection<T>(&mut self, len: usize) -> Result<()> {
+        Self::check(len <= self.limits.max_len, Limit::Len)?;
+        self.alloc(len.saturating_mul(size_of::<T>()))
+    }
+
+    /// Checks a string of `len` bytes, Borrowed strings don't allocate.
+    #[inline]
+    pub fn string(&mut self, len: usize, owned: bool) -> Result<()> {
+        Self::check(len <= self.limits.max_str_len, Limit::StrLen)?;
+        self.alloc(if owned { len } else { 0 })
+    }
+
+    /// Enters a nested derived type, The depth is restored when the guard is dropped.
+    #[inline]
+    pub fn nested(&mut self) -> Result<Nested<'_>> {
+        self.depth += 1;
+        // Dropping the guard on error, also restores the depth.
+        let nested = Nested(self);
+        Self::check(nested.0.depth <= nested.0.limits.max_depth, Limit::Depth)?;
+        Ok(nested)
+    }
+}
+
+/// Guard of a nested derived type, See [Budget::nested].
+pub struct Nested<'a>(&'a mut Budget);
+
+impl Deref for Nested<'_> {
+    type Target = Budget;
+    #[inline]
+    fn deref(&self) -> &Budget {
+        self.0
+    }
+}
+
+impl DerefMut for Nested<'_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Budget {
+        self.0
+    }
+}
+
+impl Drop for Nested<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.depth -= 1;
+    }
+}
@@ -1,6 +1,8 @@
 use super::*;
+use alloc::collections::*;
+#[cfg(feature = "std")]
 use std::{
-    collections::*,
+    collections::{HashMap, HashSet},
     hash::{BuildHasher, Hash},
 };
 
@@ -8,19 +10,37 @@ macro_rules! impl_v2 {
     [Encode for $name: ty where $($ty: tt)*] => {
         impl<$($ty)*> Encode for $name { impl_v2! {@EncoderBody} }
     };
+    [MaxEncodedSize for $name: ty where $($ty: tt)*] => {
+        impl<$($ty)*, const CONFIG: u16> MaxEncodedSize<CONFIG> for $name {
+            const MAX_ENCODED_SIZE: Option<usize> = None;
+        }
+    };
     [Decode for $name: ty where $($ty: tt)*] => {
         impl<'de, $($ty)*> Decode<'de> for $name { impl_v2! {@DecoderBody} }
     };
+    [DecodeFromReader for $name: ty where $($ty: tt)*] => {
+        impl<$($ty)*> DecodeFromReader for $name { impl_v2! {@ReaderBody} }
+    };
     [@EncoderBody] => {
         fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
             encode_len!(self, c);
             self.iter().try_for_each(|item| item.encode::<CONFIG>(c))
         }
+        fn encoded_len<const CONFIG: u16>(&self) -> usize {
+            encoded_len_of_len!(self.len()) + self.iter().map(|item| item.encoded_len::<CONFIG>()).sum::<usize>()
+        }
     };
     [@DecoderBody] => {
-        fn decode<const CONFIG: u16>(c: &mut &'de [u8]) -> Result<Self> {
+        utils::decode_unlimited!('de);
+        fn decode_limited<const CONFIG: u16>(c: &mut &'de [u8], budget: &mut Budget) -> Result<Self> {
             let len = decode_len!(c);
-            utils::try_collect::<_, _, CONFIG>(c, len)
+            utils::try_collect::<_, _, CONFIG>(c, len, budget)
+        }
+    };
+    [@ReaderBody] => {
+        fn decode_from_reader_limited<const CONFIG: u16>(r: &mut (impl Read + ?Sized), budget: &mut Budget) -> Result<Self> {
+            let len = decode_len!(@reader r);
+            utils::try_collect_from_reader::<_, _, CONFIG>(r, len, budget)
         }
     };
 }
@@ -35,14 +55,44 @@ impl_v2!(Encode for LinkedList<T>      where T: Encode);
 impl_v2!(Encode for BinaryHeap<T>      where T: Encode);
 impl_v2!(Encode for BTreeSet<T>        where T: Encode);
 impl_v2!(Encode for BTreeMap<K, V>     where K: Encode, V: Encode);
+#[cfg(feature = "std")]
 impl_v2!(Encode for HashSet<T, S>      where T: Encode, S);
+#[cfg(feature = "std")]
 impl_v2!(Encode for HashMap<K, V, S>   where K: Encode, V: Encode, S);
 
+impl<T, const CONFIG: u16> MaxEncodedSize<CONFIG> for [T] {
+    const MAX_ENCODED_SIZE: Option<usize> = None;
+}
+
+impl_v2!(MaxEncodedSize for Vec<T>             where T);
+impl_v2!(MaxEncodedSize for VecDeque<T>        where T);
+impl_v2!(MaxEncodedSize for LinkedList<T>      where T);
+impl_v2!(MaxEncodedSize for BinaryHeap<T>      where T);
+impl_v2!(MaxEncodedSize for BTreeSet<T>        where T);
+impl_v2!(MaxEncodedSize for BTreeMap<K, V>     where K, V);
+#[cfg(feature = "std")]
+impl_v2!(MaxEncodedSize for HashSet<T, S>      where T, S);
+#[cfg(feature = "std")]
+impl_v2!(MaxEncodedSize for HashMap<K, V, S>   where K, V, S);
+
 impl_v2!(Decode for Vec<T>             where T: Decode<'de>);
 impl_v2!(Decode for VecDeque<T>        where T: Decode<'de>);
 impl_v2!(Decode for LinkedList<T>      where T: Decode<'de>);
 impl_v2!(Decode for BinaryHeap<T>      where T: Decode<'de> + Ord);
 impl_v2!(Decode for BTreeSet<T>        where T: Decode<'de> + Ord);
 impl_v2!(Decode for BTreeMap<K, V>     where K: Decode<'de> + Ord, V: Decode<'de>);
+#[cfg(feature = "std")]
 impl_v2!(Decode for HashSet<T, S>      where T: Decode<'de> + Eq + Hash, S: BuildHasher + Default);
+#[cfg(feature = "std")]
 impl_v2!(Decode for HashMap<K, V, S>   where K: Decode<'de> + Eq + Hash, V: Decode<'de>, S: BuildHasher + Default);
+
+impl_v2!(DecodeFromReader for Vec<T>             where T: DecodeFromReader);
+impl_v2!(DecodeFromReader for VecDeque<T>        where T: DecodeFromReader);
+impl_v2!(DecodeFromReader for LinkedList<T>      where T: DecodeFromReader);
+impl_v2!(DecodeFromReader for BinaryHeap<T>      where T: DecodeFromReader + Ord);
+impl_v2!(DecodeFromReader for BTreeSet<T>        where T: DecodeFromReader + Ord);
+impl_v2!(DecodeFromReader for BTreeMap<K, V>     where K: DecodeFromReader + Ord, V: DecodeFromReader);
+#[cfg(feature = "std")]
+impl_v2!(DecodeFromReader for HashSet<T, S>      where T: DecodeFromReader + Eq + Hash, S: BuildHasher + Default);
+#[cfg(feature = "std")]
+impl_v2!(DecodeFromReader for HashMap<K, V, S>   where K: DecodeFromReader + Eq + Hash, V: DecodeFromReader, S: BuildHasher + Default);
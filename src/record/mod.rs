@@ -5,6 +5,28 @@ use crate::*;
 mod collection;
 mod string;
 
+/// Fixed-width length, In the byte order of [config::num] option.
+/// Variable-length [config::num] options use little endian byte order.
+macro_rules! fixed_len {
+    [@encode $ty:ty, $len:expr, $c:expr] => {{
+        let len = <$ty>::try_from($len).map_err(|_| utils::invalid_input(error::IntegerOverflow))?;
+        $c.write_all(&match CONFIG & config::num::GET {
+            config::num::BE => len.to_be_bytes(),
+            config::num::NE => len.to_ne_bytes(),
+            _ => len.to_le_bytes(),
+        })?
+    }};
+    [@decode $ty:ty, $c:expr] => {{
+        use utils::Input;
+        let bytes = $c.take_array()?;
+        usize::try_from(match CONFIG & config::num::GET {
+            config::num::BE => <$ty>::from_be_bytes(bytes),
+            config::num::NE => <$ty>::from_ne_bytes(bytes),
+            _ => <$ty>::from_le_bytes(bytes),
+        })?
+    }};
+}
+
 macro_rules! encode_len {
     [$data:expr, $c: expr] => {
         let len = $data.len();
@@ -13,20 +35,82 @@ macro_rules! encode_len {
             config::len::BEU29 => var_int::BEU29::try_from(len).map_err(utils::invalid_input)?.encode::<CONFIG>($c)?,
             config::len::BEU22 => var_int::BEU22::try_from(len).map_err(utils::invalid_input)?.encode::<CONFIG>($c)?,
             config::len::BEU15 => var_int::BEU15::try_from(len).map_err(utils::invalid_input)?.encode::<CONFIG>($c)?,
+            config::len::BEU62 => var_int::BEU62::try_from(len).map_err(utils::invalid_input)?.encode::<CONFIG>($c)?,
+            config::len::VLQ => var_int::VLQ::try_from(len).map_err(utils::invalid_input)?.encode::<CONFIG>($c)?,
+            config::len::COMPACT_SIZE => var_int::CompactSize::try_from(len).map_err(utils::invalid_input)?.encode::<CONFIG>($c)?,
+            config::len::SQLITE_VARINT => var_int::SqliteVarint::try_from(len).map_err(utils::invalid_input)?.encode::<CONFIG>($c)?,
+            config::len::PREFIX_VARINT => var_int::PrefixVarint::try_from(len).map_err(utils::invalid_input)?.encode::<CONFIG>($c)?,
+            config::len::U8 => fixed_len!(@encode u8, len, $c),
+            config::len::U16 => fixed_len!(@encode u16, len, $c),
+            config::len::U32 => fixed_len!(@encode u32, len, $c),
+            config::len::U64 => fixed_len!(@encode u64, len, $c),
+            config::len::LEB128 => (len as u64).encode::<{ config::num::LEB128 }>($c)?,
+            _ => unreachable!()
+        }
+    };
+}
+macro_rules! encoded_len_of_len {
+    [$len: expr] => {
+        match CONFIG & config::len::GET {
+            config::len::BEU30 => var_int::BEU30::try_from($len).map_or(0, |len| len.encoded_len::<CONFIG>()),
+            config::len::BEU29 => var_int::BEU29::try_from($len).map_or(0, |len| len.encoded_len::<CONFIG>()),
+            config::len::BEU22 => var_int::BEU22::try_from($len).map_or(0, |len| len.encoded_len::<CONFIG>()),
+            config::len::BEU15 => var_int::BEU15::try_from($len).map_or(0, |len| len.encoded_len::<CONFIG>()),
+            config::len::BEU62 => var_int::BEU62::try_from($len).map_or(0, |len| len.encoded_len::<CONFIG>()),
+            config::len::VLQ => var_int::VLQ::try_from($len).map_or(0, |len| len.encoded_len::<CONFIG>()),
+            config::len::COMPACT_SIZE => var_int::CompactSize::try_from($len).map_or(0, |len| len.encoded_len::<CONFIG>()),
+            config::len::SQLITE_VARINT => var_int::SqliteVarint::try_from($len).map_or(0, |len| len.encoded_len::<CONFIG>()),
+            config::len::PREFIX_VARINT => var_int::PrefixVarint::try_from($len).map_or(0, |len| len.encoded_len::<CONFIG>()),
+            config::len::U8 => size_of::<u8>(),
+            config::len::U16 => size_of::<u16>(),
+            config::len::U32 => size_of::<u32>(),
+            config::len::U64 => size_of::<u64>(),
+            config::len::LEB128 => utils::leb128_len($len as u128),
             _ => unreachable!()
         }
     };
 }
 macro_rules! decode_len {
+    [@reader $r: expr] => {
+        match CONFIG & config::len::GET {
+            config::len::BEU30 => { usize::try_from(var_int::BEU30::decode_from_reader::<CONFIG>($r)?)? }
+            config::len::BEU29 => { usize::try_from(var_int::BEU29::decode_from_reader::<CONFIG>($r)?)? }
+            config::len::BEU22 => { usize::try_from(var_int::BEU22::decode_from_reader::<CONFIG>($r)?)? }
+            config::len::BEU15 => { usize::try_from(var_int::BEU15::decode_from_reader::<CONFIG>($r)?)? }
+            config::len::BEU62 => { usize::try_from(var_int::BEU62::decode_from_reader::<CONFIG>($r)?)? }
+            config::len::VLQ => { usize::try_from(var_int::VLQ::decode_from_reader::<CONFIG>($r)?)? }
+            config::len::COMPACT_SIZE => { usize::try_from(var_int::CompactSize::decode_from_reader::<CONFIG>($r)?)? }
+            config::len::SQLITE_VARINT => { usize::try_from(var_int::SqliteVarint::decode_from_reader::<CONFIG>($r)?)? }
+            config::len::PREFIX_VARINT => { usize::try_from(var_int::PrefixVarint::decode_from_reader::<CONFIG>($r)?)? }
+            config::len::U8 => fixed_len!(@decode u8, (&mut utils::Reader($r))),
+            config::len::U16 => fixed_len!(@decode u16, (&mut utils::Reader($r))),
+            config::len::U32 => fixed_len!(@decode u32, (&mut utils::Reader($r))),
+            config::len::U64 => fixed_len!(@decode u64, (&mut utils::Reader($r))),
+            config::len::LEB128 => { usize::try_from(u64::decode_from_reader::<{ config::num::LEB128 }>($r)?)? }
+            _ => unreachable!()
+        }
+    };
     [$c: expr] => {
         match CONFIG & config::len::GET {
             config::len::BEU30 => { usize::try_from(var_int::BEU30::decode::<CONFIG>($c)?)? }
             config::len::BEU29 => { usize::try_from(var_int::BEU29::decode::<CONFIG>($c)?)? }
             config::len::BEU22 => { usize::try_from(var_int::BEU22::decode::<CONFIG>($c)?)? }
             config::len::BEU15 => { usize::try_from(var_int::BEU15::decode::<CONFIG>($c)?)? }
+            config::len::BEU62 => { usize::try_from(var_int::BEU62::decode::<CONFIG>($c)?)? }
+            config::len::VLQ => { usize::try_from(var_int::VLQ::decode::<CONFIG>($c)?)? }
+            config::len::COMPACT_SIZE => { usize::try_from(var_int::CompactSize::decode::<CONFIG>($c)?)? }
+            config::len::SQLITE_VARINT => { usize::try_from(var_int::SqliteVarint::decode::<CONFIG>($c)?)? }
+            config::len::PREFIX_VARINT => { usize::try_from(var_int::PrefixVarint::decode::<CONFIG>($c)?)? }
+            config::len::U8 => fixed_len!(@decode u8, $c),
+            config::len::U16 => fixed_len!(@decode u16, $c),
+            config::len::U32 => fixed_len!(@decode u32, $c),
+            config::len::U64 => fixed_len!(@decode u64, $c),
+            config::len::LEB128 => { usize::try_from(u64::decode::<{ config::num::LEB128 }>($c)?)? }
             _ => unreachable!()
         }
     };
 }
 pub(crate) use decode_len;
 pub(crate) use encode_len;
+pub(crate) use encoded_len_of_len;
+pub(crate) use fixed_len;
@@ -1,4 +1,5 @@
 use super::*;
+use alloc::string::ToString;
 
 macro_rules! impl_encoder_for {
     [$($ty:ty),*] => {$(
@@ -7,11 +8,22 @@ macro_rules! impl_encoder_for {
                 encode_len!(self, c);
                 c.write_all(self.as_ref())
             }
+            #[inline] fn encoded_len<const CONFIG: u16>(&self) -> usize {
+                encoded_len_of_len!(self.len()) + self.len()
+            }
         }
     )*};
 }
 impl_encoder_for!(str, String);
 
+impl<const CONFIG: u16> MaxEncodedSize<CONFIG> for str {
+    const MAX_ENCODED_SIZE: Option<usize> = None;
+}
+
+impl<const CONFIG: u16> MaxEncodedSize<CONFIG> for String {
+    const MAX_ENCODED_SIZE: Option<usize> = None;
+}
+
 macro_rules! impl_encoder_for_trait_obj {
     [$($ty:ty);*] => {$(
         impl Encode for $ty {
@@ -21,31 +33,57 @@ macro_rules! impl_encoder_for_trait_obj {
                 encode_len!(string, c);
                 c.write_all(string.as_ref())
             }
+            #[inline]
+            fn encoded_len<const CONFIG: u16>(&self) -> usize {
+                let len = self.to_string().len();
+                encoded_len_of_len!(len) + len
+            }
         }
     )*};
 }
-impl_encoder_for_trait_obj!(Box<dyn std::fmt::Display>; Box<dyn std::error::Error>; Box<dyn std::error::Error + Send + Sync>);
+impl_encoder_for_trait_obj!(Box<dyn core::fmt::Display>; Box<dyn core::error::Error>; Box<dyn core::error::Error + Send + Sync>);
 
 macro_rules! read_slice {
     [$c: expr] => ({
         let len = decode_len!($c);
         utils::get_slice($c, len)
     });
+    [@str $c: expr, $budget: expr, $owned: expr] => ({
+        let len = decode_len!($c);
+        $budget.string(len, $owned)?;
+        utils::get_slice($c, len)
+    });
 }
 
 impl<'de> Decode<'de> for String {
+    utils::decode_unlimited!('de);
     #[inline]
-    fn decode<const CONFIG: u16>(c: &mut &'de [u8]) -> Result<Self> {
-        let data = read_slice!(c)?;
-        String::from_utf8(data.to_vec()).map_err(Error::from)
+    fn decode_limited<const CONFIG: u16>(c: &mut &'de [u8], budget: &mut Budget) -> Result<Self> {
+        let data = read_slice!(@str c, budget, true)?;
+        core::str::from_utf8(data)
+            .map(ToString::to_string)
+            .map_err(|err| Error::invalid_utf8(data, err))
+    }
+}
+
+impl DecodeFromReader for String {
+    #[inline]
+    fn decode_from_reader_limited<const CONFIG: u16>(
+        r: &mut (impl Read + ?Sized),
+        budget: &mut Budget,
+    ) -> Result<Self> {
+        let len = decode_len!(@reader r);
+        budget.string(len, true)?;
+        String::from_utf8(utils::read_bytes(r, len)?).map_err(Error::from)
     }
 }
 
 impl<'de: 'a, 'a> Decode<'de> for &'a str {
+    utils::decode_unlimited!('de);
     #[inline]
-    fn decode<const CONFIG: u16>(c: &mut &'de [u8]) -> Result<Self> {
-        let data = read_slice!(c)?;
-        std::str::from_utf8(data).map_err(Error::from)
+    fn decode_limited<const CONFIG: u16>(c: &mut &'de [u8], budget: &mut Budget) -> Result<Self> {
+        let data = read_slice!(@str c, budget, false)?;
+        core::str::from_utf8(data).map_err(|err| Error::invalid_utf8(data, err))
     }
 }
 
@@ -0,0 +1,69 @@
+//! Compile-time upper bound of encoded size.
+//!
+//! [MaxEncodedSize] is implemented for scalars, arrays, tuples, `Option`, etc..
+//! and is generated by `#[derive(Encode)]`. Collections and strings report `None` (unbounded).
+//!
+//! ### Example
+//!
+//! ```
+//! use databuf::{Encode, MaxEncodedSize, config::num::{BE, LEB128}};
+//!
+//! #[derive(Encode)]
+//! struct Packet {
+//!     id: u32,
+//!     flags: [bool; 2],
+//!     checksum: Option<u16>,
+//! }
+//! assert_eq!(<Packet as MaxEncodedSize<BE>>::MAX_ENCODED_SIZE, Some(4 + 2 + 3));
+//! assert_eq!(<Packet as MaxEncodedSize<LEB128>>::MAX_ENCODED_SIZE, Some(5 + 2 + 4));
+//!
+//! let packet = Packet { id: 42, flags: [true, false], checksum: None };
+//! let (buf, len) = packet.to_array::<BE, 9>();
+//! assert_eq!(buf[..len], [0, 0, 0, 42, 1, 0, 0]);
+//! ```
+
+/// This trait provides the maximum number of bytes, a type may be encoded into with the given `CONFIG`.
+pub trait MaxEncodedSize<const CONFIG: u16> {
+    /// Maximum encoded size in bytes, `None` if the size is unbounded.
+    const MAX_ENCODED_SIZE: Option<usize>;
+}
+
+/// Sum of two sizes, `None` if any of them is unbounded (or overflows).
+pub const fn add(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (Some(a), Some(b)) => a.checked_add(b),
+        _ => None,
+    }
+}
+
+/// Larger of two sizes, `None` if any of them is unbounded.
+pub const fn max(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (Some(a), Some(b)) if a > b => Some(a),
+        (Some(_), Some(b)) => Some(b),
+        _ => None,
+    }
+}
+
+/// Size of `n` repetitions, `None` if unbounded (or overflows).
+pub const fn mul(size: Option<usize>, n: usize) -> Option<usize> {
+    match size {
+        Some(size) => size.checked_mul(n),
+        None => None,
+    }
+}
+
+/// Maximum size of a [LEB128](crate::config::num::LEB128) encoded integer of `bits` width.
+pub(crate) const fn leb128(bits: u32) -> usize {
+    (bits as usize).div_ceil(7)
+}
+
+/// Number of bytes written by `encode`, Used by `#[databuf(with = ...)]` fields.
+#[doc(hidden)]
+#[inline]
+pub fn len_of(encode: impl FnOnce(&mut crate::utils::Counter) -> crate::io::Result<()>) -> usize {
+    let mut counter = crate::utils::Counter(0);
+    // Counter never fails, Errors are reported by `encode` itself.
+    let _ = encode(&mut counter);
+    counter.0
+}
@@ -0,0 +1,276 @@
+//! #### Alternative String Encodings
+//!
+//! [String] is encoded as length-prefixed UTF-8, Wrapper types of this module encode it differently:
+//!
+//! - [NulTerminated]: UTF-8 bytes followed by a `0` byte, Like C strings.
+//! - [Utf16LE] and [Utf16BE]: UTF-16 code units, Length is the number of code units.
+//! - [Latin1]: ISO-8859-1, One byte per character.
+//! - [LossyUtf8]: Same as [String], But invalid UTF-8 is replaced with `U+FFFD` while decoding.
+//!
+//! Length prefixes follow the [len](crate::config::len) option.
+//!
+//! ### Example
+//!
+//! ```
+//! use databuf::{*, config::num::LE, text::*};
+//!
+//! assert_eq!(NulTerminated::from("Hi").to_bytes::<LE>(), [b'H', b'i', 0]);
+//! assert_eq!(Utf16LE::from("Hi").to_bytes::<LE>(), [2, b'H', 0, b'i', 0]);
+//! assert_eq!(Latin1::from("é").to_bytes::<LE>(), [1, 0xE9]);
+//!
+//! let text = LossyUtf8::from_bytes::<LE>(&[3, b'H', 0xFF, b'i']).unwrap();
+//! assert_eq!(text.0, "H\u{FFFD}i");
+//! ```
+
+use crate::record::{decode_len, encode_len, encoded_len_of_len, fixed_len};
+use crate::*;
+use core::{fmt, ops::Deref};
+
+macro_rules! def {
+    [$($(#[$doc:meta])* $name:ident;)*] => {$(
+        $(#[$doc])*
+        #[derive(Default, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name(pub String);
+        impl<const CONFIG: u16> MaxEncodedSize<CONFIG> for $name {
+            const MAX_ENCODED_SIZE: Option<usize> = None;
+        }
+        impl From<String> for $name {
+            fn from(string: String) -> Self { Self(string) }
+        }
+        impl From<&str> for $name {
+            fn from(string: &str) -> Self { Self(string.into()) }
+        }
+        impl From<$name> for String {
+            fn from(string: $name) -> Self { string.0 }
+        }
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { self.0.fmt(f) }
+        }
+        impl Deref for $name {
+            type Target = String;
+            #[inline] fn deref(&self) -> &Self::Target { &self.0 }
+        }
+        impl core::ops::DerefMut for $name {
+            #[inline] fn deref_mut(&mut self) -> &mut Self::Target { &mut self.0 }
+        }
+    )*};
+}
+
+def! {
+    /// UTF-8 string followed by a `0` byte, Without length prefix.
+    ///
+    /// Encoding a string that contains `'\0'` fails with [error::InteriorNul].
+    NulTerminated;
+    /// UTF-16 little endian string, Length is the number of code units.
+    ///
+    /// Unpaired surrogates fail with [ErrorKind::InvalidChar](error::ErrorKind::InvalidChar).
+    Utf16LE;
+    /// UTF-16 big endian string, Length is the number of code units.
+    ///
+    /// Unpaired surrogates fail with [ErrorKind::InvalidChar](error::ErrorKind::InvalidChar).
+    Utf16BE;
+    /// ISO-8859-1 string, Each character is a single byte.
+    ///
+    /// Encoding a character above `U+00FF` fails with [error::InvalidChar].
+    Latin1;
+    /// UTF-8 string, Invalid sequences are replaced with `U+FFFD` while decoding, Instead of failing.
+    LossyUtf8;
+}
+
+impl Encode for NulTerminated {
+    #[inline]
+    fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
+        if self.0.contains('\0') {
+            return Err(utils::invalid_input(error::InteriorNul));
+        }
+        c.write_all(self.0.as_bytes())?;
+        c.write_all(&[0])
+    }
+    #[inline]
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        self.0.len() + 1
+    }
+}
+
+impl Decode<'_> for NulTerminated {
+    utils::decode_unlimited!('_);
+    #[inline]
+    fn decode_limited<const CONFIG: u16>(c: &mut &[u8], budget: &mut Budget) -> Result<Self> {
+        let len = c
+            .iter()
+            .position(|&byte| byte == 0)
+            .ok_or_else(|| Error::insufficient_bytes(1))?;
+        budget.string(len, true)?;
+        let data = utils::get_slice(c, len + 1)?;
+        from_utf8(&data[..len]).map(Self)
+    }
+}
+
+impl DecodeFromReader for NulTerminated {
+    #[inline]
+    fn decode_from_reader_limited<const CONFIG: u16>(
+        r: &mut (impl Read + ?Sized),
+        budget: &mut Budget,
+    ) -> Result<Self> {
+        let max_len = budget.limits().max_str_len;
+        let mut data = Vec::new();
+        loop {
+            match u8::decode_from_reader::<CONFIG>(r)? {
+                0 => break,
+                _ if data.len() == max_len => {
+                    return Err(Error::new(error::ErrorKind::LimitExceeded(
+                        error::Limit::StrLen,
+                    )));
+                }
+                byte => data.push(byte),
+            }
+        }
+        budget.string(data.len(), true)?;
+        String::from_utf8(data).map(Self).map_err(Error::from)
+    }
+}
+
+#[inline]
+fn from_utf8(data: &[u8]) -> Result<String> {
+    core::str::from_utf8(data)
+        .map(Into::into)
+        .map_err(|err| Error::invalid_utf8(data, err))
+}
+
+macro_rules! impl_utf16 {
+    [$($name:ident($to_bytes:ident, $from_bytes:ident))*] => {$(
+        impl Encode for $name {
+            #[inline]
+            fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
+                let units: Vec<u16> = self.0.encode_utf16().collect();
+                encode_len!(units, c);
+                units.iter().try_for_each(|unit| c.write_all(&unit.$to_bytes()))
+            }
+            #[inline]
+            fn encoded_len<const CONFIG: u16>(&self) -> usize {
+                let len = self.0.encode_utf16().count();
+                encoded_len_of_len!(len) + len * 2
+            }
+        }
+        impl Decode<'_> for $name {
+            utils::decode_unlimited!('_);
+            #[inline]
+            fn decode_limited<const CONFIG: u16>(c: &mut &[u8], budget: &mut Budget) -> Result<Self> {
+                let len = decode_len!(c).saturating_mul(2);
+                budget.string(len, true)?;
+                from_utf16(utils::get_slice(c, len)?, u16::$from_bytes).map(Self)
+            }
+        }
+        impl DecodeFromReader for $name {
+            #[inline]
+            fn decode_from_reader_limited<const CONFIG: u16>(r: &mut (impl Read + ?Sized), budget: &mut Budget) -> Result<Self> {
+                let len = decode_len!(@reader r).saturating_mul(2);
+                budget.string(len, true)?;
+                from_utf16(&utils::read_bytes(r, len)?, u16::$from_bytes).map(Self)
+            }
+        }
+    )*};
+}
+impl_utf16!(Utf16LE(to_le_bytes, from_le_bytes) Utf16BE(to_be_bytes, from_be_bytes));
+
+#[inline]
+fn from_utf16(data: &[u8], from_bytes: fn([u8; 2]) -> u16) -> Result<String> {
+    let units = data
+        .chunks_exact(2)
+        .map(|unit| from_bytes([unit[0], unit[1]]));
+    char::decode_utf16(units)
+        .collect::<core::result::Result<_, _>>()
+        .map_err(|err| {
+            Error::new(error::ErrorKind::InvalidChar(
+                err.unpaired_surrogate().into(),
+            ))
+        })
+}
+
+impl Encode for Latin1 {
+    #[inline]
+    fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
+        let data: Vec<u8> = self
+            .0
+            .chars()
+            .map(u8::try_from)
+            .collect::<core::result::Result<_, _>>()
+            .map_err(|_| utils::invalid_input(error::InvalidChar))?;
+        encode_len!(data, c);
+        c.write_all(&data)
+    }
+    #[inline]
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        let len = self.0.chars().count();
+        encoded_len_of_len!(len) + len
+    }
+}
+
+impl Decode<'_> for Latin1 {
+    utils::decode_unlimited!('_);
+    #[inline]
+    fn decode_limited<const CONFIG: u16>(c: &mut &[u8], budget: &mut Budget) -> Result<Self> {
+        let len = decode_len!(c);
+        budget.string(len, true)?;
+        Ok(Self(
+            utils::get_slice(c, len)?
+                .iter()
+                .map(|&byte| char::from(byte))
+                .collect(),
+        ))
+    }
+}
+
+impl DecodeFromReader for Latin1 {
+    #[inline]
+    fn decode_from_reader_limited<const CONFIG: u16>(
+        r: &mut (impl Read + ?Sized),
+        budget: &mut Budget,
+    ) -> Result<Self> {
+        let len = decode_len!(@reader r);
+        budget.string(len, true)?;
+        Ok(Self(
+            utils::read_bytes(r, len)?
+                .into_iter()
+                .map(char::from)
+                .collect(),
+        ))
+    }
+}
+
+impl Encode for LossyUtf8 {
+    #[inline]
+    fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
+        self.0.encode::<CONFIG>(c)
+    }
+    #[inline]
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        self.0.encoded_len::<CONFIG>()
+    }
+}
+
+impl Decode<'_> for LossyUtf8 {
+    utils::decode_unlimited!('_);
+    #[inline]
+    fn decode_limited<const CONFIG: u16>(c: &mut &[u8], budget: &mut Budget) -> Result<Self> {
+        let len = decode_len!(c);
+        budget.string(len, true)?;
+        let data = utils::get_slice(c, len)?;
+        Ok(Self(String::from_utf8_lossy(data).into_owned()))
+    }
+}
+
+impl DecodeFromReader for LossyUtf8 {
+    #[inline]
+    fn decode_from_reader_limited<const CONFIG: u16>(
+        r: &mut (impl Read + ?Sized),
+        budget: &mut Budget,
+    ) -> Result<Self> {
+        let len = decode_len!(@reader r);
+        budget.string(len, true)?;
+        let data = utils::read_bytes(r, len)?;
+        Ok(Self(String::from_utf8(data).unwrap_or_else(|err| {
+            String::from_utf8_lossy(err.as_bytes()).into_owned()
+        })))
+    }
+}
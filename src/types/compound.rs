@@ -1,4 +1,4 @@
-use std::convert::TryFrom;
+use core::convert::TryFrom;
 
 use crate::*;
 
@@ -12,13 +12,35 @@ macro_rules! impl_data_type_for_typle {
                 $(self.$idx.encode::<CONFIG>(_c)?;)*
                 Ok(())
             }
+            #[inline] fn encoded_len<const CONFIG: u16>(&self) -> usize {
+                0 $(+ self.$idx.encoded_len::<CONFIG>())*
+            }
+        }
+        impl<$($name,)* const CONFIG: u16> MaxEncodedSize<CONFIG> for ($($name,)*)
+        where
+            $($name: MaxEncodedSize<CONFIG>,)*
+        {
+            const MAX_ENCODED_SIZE: Option<usize> = {
+                let size = Some(0);
+                $(let size = size::add(size, $name::MAX_ENCODED_SIZE);)*
+                size
+            };
         }
         impl<'de, $($name,)*> Decode<'de> for ($($name,)*)
         where
             $($name: Decode<'de>,)*
         {
-            #[inline] fn decode<const CONFIG: u16>(_c: &mut &'de [u8]) -> Result<Self> {
-                Ok(($($name::decode::<CONFIG>(_c)?,)*))
+            utils::decode_unlimited!('de);
+            #[inline] fn decode_limited<const CONFIG: u16>(_c: &mut &'de [u8], _budget: &mut Budget) -> Result<Self> {
+                Ok(($($name::decode_limited::<CONFIG>(_c, _budget)?,)*))
+            }
+        }
+        impl<$($name,)*> DecodeFromReader for ($($name,)*)
+        where
+            $($name: DecodeFromReader,)*
+        {
+            #[inline] fn decode_from_reader_limited<const CONFIG: u16>(_r: &mut (impl Read + ?Sized), _budget: &mut Budget) -> Result<Self> {
+                Ok(($($name::decode_from_reader_limited::<CONFIG>(_r, _budget)?,)*))
             }
         }
     )*);
@@ -52,18 +74,54 @@ where
     fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
         self.iter().try_for_each(|item| item.encode::<CONFIG>(c))
     }
+
+    #[inline]
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        self.iter().map(T::encoded_len::<CONFIG>).sum()
+    }
+}
+
+impl<T, const N: usize, const CONFIG: u16> MaxEncodedSize<CONFIG> for [T; N]
+where
+    T: MaxEncodedSize<CONFIG>,
+{
+    const MAX_ENCODED_SIZE: Option<usize> = size::mul(T::MAX_ENCODED_SIZE, N);
 }
 
 impl<'de, T, const N: usize> Decode<'de> for [T; N]
 where
     T: Decode<'de>,
 {
+    utils::decode_unlimited!('de);
     #[inline]
-    fn decode<const CONFIG: u16>(cursor: &mut &'de [u8]) -> Result<Self> {
-        utils::try_collect::<T, Vec<_>, CONFIG>(cursor, N).map(|vec| unsafe {
-            debug_assert_eq!(vec.len(), N);
-            <[T; N]>::try_from(vec).unwrap_unchecked()
-        })
+    fn decode_limited<const CONFIG: u16>(
+        cursor: &mut &'de [u8],
+        budget: &mut Budget,
+    ) -> Result<Self> {
+        utils::collect::<T, Vec<_>>(N, || T::decode_limited::<CONFIG>(cursor, budget)).map(
+            |vec| unsafe {
+                debug_assert_eq!(vec.len(), N);
+                <[T; N]>::try_from(vec).unwrap_unchecked()
+            },
+        )
+    }
+}
+
+impl<T, const N: usize> DecodeFromReader for [T; N]
+where
+    T: DecodeFromReader,
+{
+    #[inline]
+    fn decode_from_reader_limited<const CONFIG: u16>(
+        r: &mut (impl Read + ?Sized),
+        budget: &mut Budget,
+    ) -> Result<Self> {
+        utils::collect::<T, Vec<_>>(N, || T::decode_from_reader_limited::<CONFIG>(r, budget)).map(
+            |vec| unsafe {
+                debug_assert_eq!(vec.len(), N);
+                <[T; N]>::try_from(vec).unwrap_unchecked()
+            },
+        )
     }
 }
 
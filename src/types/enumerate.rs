@@ -14,19 +14,46 @@ where
             None => c.write_all(&[0]),
         }
     }
+    #[inline]
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        1 + self.as_ref().map_or(0, T::encoded_len::<CONFIG>)
+    }
+}
+
+impl<T, const CONFIG: u16> MaxEncodedSize<CONFIG> for Option<T>
+where
+    T: MaxEncodedSize<CONFIG>,
+{
+    const MAX_ENCODED_SIZE: Option<usize> = size::add(Some(1), T::MAX_ENCODED_SIZE);
 }
 
 impl<'de, T: Decode<'de>> Decode<'de> for Option<T> {
+    utils::decode_unlimited!('de);
     #[inline]
-    fn decode<const CONFIG: u16>(r: &mut &'de [u8]) -> Result<Self> {
-        Ok(match bool::decode::<CONFIG>(r)? {
-            true => Some(T::decode::<CONFIG>(r)?),
+    fn decode_limited<const CONFIG: u16>(r: &mut &'de [u8], budget: &mut Budget) -> Result<Self> {
+        Ok(match bool::decode_limited::<CONFIG>(r, budget)? {
+            true => Some(T::decode_limited::<CONFIG>(r, budget)?),
             false => None,
         })
     }
 }
 
-impl<T, E> Encode for std::result::Result<T, E>
+impl<T: DecodeFromReader> DecodeFromReader for Option<T> {
+    #[inline]
+    fn decode_from_reader_limited<const CONFIG: u16>(
+        r: &mut (impl Read + ?Sized),
+        budget: &mut Budget,
+    ) -> Result<Self> {
+        Ok(
+            match bool::decode_from_reader_limited::<CONFIG>(r, budget)? {
+                true => Some(T::decode_from_reader_limited::<CONFIG>(r, budget)?),
+                false => None,
+            },
+        )
+    }
+}
+
+impl<T, E> Encode for core::result::Result<T, E>
 where
     T: Encode,
     E: Encode,
@@ -44,18 +71,54 @@ where
             }
         }
     }
+    #[inline]
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        1 + match self {
+            Ok(val) => val.encoded_len::<CONFIG>(),
+            Err(err) => err.encoded_len::<CONFIG>(),
+        }
+    }
+}
+
+impl<T, E, const CONFIG: u16> MaxEncodedSize<CONFIG> for core::result::Result<T, E>
+where
+    T: MaxEncodedSize<CONFIG>,
+    E: MaxEncodedSize<CONFIG>,
+{
+    const MAX_ENCODED_SIZE: Option<usize> =
+        size::add(Some(1), size::max(T::MAX_ENCODED_SIZE, E::MAX_ENCODED_SIZE));
 }
 
-impl<'de, T, E> Decode<'de> for std::result::Result<T, E>
+impl<'de, T, E> Decode<'de> for core::result::Result<T, E>
 where
     T: Decode<'de>,
     E: Decode<'de>,
 {
+    utils::decode_unlimited!('de);
     #[inline]
-    fn decode<const CONFIG: u16>(c: &mut &'de [u8]) -> Result<Self> {
-        Ok(match bool::decode::<CONFIG>(c)? {
-            true => Ok(T::decode::<CONFIG>(c)?),
-            false => Err(E::decode::<CONFIG>(c)?),
+    fn decode_limited<const CONFIG: u16>(c: &mut &'de [u8], budget: &mut Budget) -> Result<Self> {
+        Ok(match bool::decode_limited::<CONFIG>(c, budget)? {
+            true => Ok(T::decode_limited::<CONFIG>(c, budget)?),
+            false => Err(E::decode_limited::<CONFIG>(c, budget)?),
         })
     }
 }
+
+impl<T, E> DecodeFromReader for core::result::Result<T, E>
+where
+    T: DecodeFromReader,
+    E: DecodeFromReader,
+{
+    #[inline]
+    fn decode_from_reader_limited<const CONFIG: u16>(
+        r: &mut (impl Read + ?Sized),
+        budget: &mut Budget,
+    ) -> Result<Self> {
+        Ok(
+            match bool::decode_from_reader_limited::<CONFIG>(r, budget)? {
+                true => Ok(T::decode_from_reader_limited::<CONFIG>(r, budget)?),
+                false => Err(E::decode_from_reader_limited::<CONFIG>(r, budget)?),
+            },
+        )
+    }
+}
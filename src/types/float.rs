@@ -0,0 +1,205 @@
+use crate::*;
+use config::{float_format, float_nan, float_order};
+use utils::{Input, Reader};
+
+enum Order {
+    LE,
+    BE,
+    NE,
+}
+
+/// Byte order of floats, See [config::float]
+const fn order<const CONFIG: u16>() -> Order {
+    match CONFIG & float_order::GET {
+        float_order::LE => Order::LE,
+        float_order::BE => Order::BE,
+        _ => match CONFIG & config::num::GET {
+            config::num::BE
+            | config::num::BEU62
+            | config::num::VLQ
+            | config::num::SQLITE_VARINT => Order::BE,
+            config::num::NE => Order::NE,
+            _ => Order::LE,
+        },
+    }
+}
+
+macro_rules! write_float {
+    ($writer:ident, $bits:expr) => {
+        match order::<CONFIG>() {
+            Order::LE => $writer.write_all(&$bits.to_le_bytes()),
+            Order::BE => $writer.write_all(&$bits.to_be_bytes()),
+            Order::NE => $writer.write_all(&$bits.to_ne_bytes()),
+        }
+    };
+}
+
+macro_rules! read_float {
+    ($c:expr, $ty:ty) => {
+        match order::<CONFIG>() {
+            Order::LE => <$ty>::from_le_bytes($c.take_array()?),
+            Order::BE => <$ty>::from_be_bytes($c.take_array()?),
+            Order::NE => <$ty>::from_ne_bytes($c.take_array()?),
+        }
+    };
+}
+
+/// Encoded size of `f32`, See [config::float::F16] and [config::float::BF16]
+const fn f32_len<const CONFIG: u16>() -> usize {
+    match CONFIG & float_format::GET {
+        float_format::F16 | float_format::BF16 => 2,
+        _ => 4,
+    }
+}
+
+/// Rounds `f32` to the nearest IEEE 754 half-precision float.
+fn f32_to_f16(num: f32) -> u16 {
+    let bits = num.to_bits();
+    let sign = (bits >> 16) as u16 & 0x8000;
+    let exp = (bits >> 23 & 0xFF) as i32;
+    let man = bits & 0x7F_FFFF;
+    if exp == 0xFF {
+        // Infinity or NaN, NaN keeps the high bits of its payload and stays quiet.
+        let nan = if man != 0 {
+            0x200 | (man >> 13) as u16
+        } else {
+            0
+        };
+        return sign | 0x7C00 | nan;
+    }
+    let exp = exp - 127 + 15;
+    if exp >= 0x1F {
+        return sign | 0x7C00;
+    }
+    // Value is `man >> shift`, Rounded to nearest, ties to even.
+    let (man, shift) = if exp <= 0 {
+        if exp < -10 {
+            return sign;
+        }
+        (man | 0x80_0000, (14 - exp) as u32)
+    } else {
+        ((exp as u32) << 23 | man, 13)
+    };
+    let half = 1 << (shift - 1);
+    let rest = man & ((1 << shift) - 1);
+    let mut half_bits = man >> shift;
+    if rest > half || (rest == half && half_bits & 1 == 1) {
+        // Carry may overflow into the exponent, That is still the right rounding.
+        half_bits += 1;
+    }
+    sign | half_bits as u16
+}
+
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = ((bits & 0x8000) as u32) << 16;
+    let exp = (bits >> 10 & 0x1F) as u32;
+    let man = (bits & 0x3FF) as u32;
+    match exp {
+        0 => {
+            // Zero or subnormal: `man * 2^-24`
+            let num = man as f32 * f32::from_bits(0x3380_0000);
+            f32::from_bits(sign | num.to_bits())
+        }
+        0x1F => f32::from_bits(sign | 0x7F80_0000 | man << 13),
+        _ => f32::from_bits(sign | (exp + 127 - 15) << 23 | man << 13),
+    }
+}
+
+/// Rounds `f32` to the nearest bfloat16.
+fn f32_to_bf16(num: f32) -> u16 {
+    let bits = num.to_bits();
+    if num.is_nan() {
+        return (bits >> 16) as u16 | 0x40;
+    }
+    let round = 0x7FFF + (bits >> 16 & 1);
+    ((bits + round) >> 16) as u16
+}
+
+fn bf16_to_f32(bits: u16) -> f32 {
+    f32::from_bits((bits as u32) << 16)
+}
+
+impl Encode for f32 {
+    #[inline]
+    fn encode<const CONFIG: u16>(&self, writer: &mut (impl Write + ?Sized)) -> io::Result<()> {
+        let num = match CONFIG & float_nan::GET {
+            float_nan::CANONICAL if self.is_nan() => f32::NAN,
+            _ => *self,
+        };
+        match CONFIG & float_format::GET {
+            float_format::F16 => write_float!(writer, f32_to_f16(num)),
+            float_format::BF16 => write_float!(writer, f32_to_bf16(num)),
+            _ => write_float!(writer, num.to_bits()),
+        }
+    }
+    #[inline]
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        f32_len::<CONFIG>()
+    }
+}
+
+impl<const CONFIG: u16> MaxEncodedSize<CONFIG> for f32 {
+    const MAX_ENCODED_SIZE: Option<usize> = Some(f32_len::<CONFIG>());
+}
+
+#[inline]
+fn decode_f32<const CONFIG: u16>(c: &mut impl Input) -> Result<f32> {
+    Ok(match CONFIG & float_format::GET {
+        float_format::F16 => f16_to_f32(read_float!(c, u16)),
+        float_format::BF16 => bf16_to_f32(read_float!(c, u16)),
+        _ => f32::from_bits(read_float!(c, u32)),
+    })
+}
+
+impl Decode<'_> for f32 {
+    #[inline]
+    fn decode<const CONFIG: u16>(c: &mut &[u8]) -> Result<Self> {
+        decode_f32::<CONFIG>(c)
+    }
+}
+
+impl DecodeFromReader for f32 {
+    #[inline]
+    fn decode_from_reader_limited<const CONFIG: u16>(
+        r: &mut (impl Read + ?Sized),
+        _: &mut Budget,
+    ) -> Result<Self> {
+        decode_f32::<CONFIG>(&mut Reader(r))
+    }
+}
+
+impl Encode for f64 {
+    #[inline]
+    fn encode<const CONFIG: u16>(&self, writer: &mut (impl Write + ?Sized)) -> io::Result<()> {
+        let num = match CONFIG & float_nan::GET {
+            float_nan::CANONICAL if self.is_nan() => f64::NAN,
+            _ => *self,
+        };
+        write_float!(writer, num.to_bits())
+    }
+    #[inline]
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        8
+    }
+}
+
+impl<const CONFIG: u16> MaxEncodedSize<CONFIG> for f64 {
+    const MAX_ENCODED_SIZE: Option<usize> = Some(8);
+}
+
+impl Decode<'_> for f64 {
+    #[inline]
+    fn decode<const CONFIG: u16>(c: &mut &[u8]) -> Result<Self> {
+        Ok(f64::from_bits(read_float!(c, u64)))
+    }
+}
+
+impl DecodeFromReader for f64 {
+    #[inline]
+    fn decode_from_reader_limited<const CONFIG: u16>(
+        r: &mut (impl Read + ?Sized),
+        _: &mut Budget,
+    ) -> Result<Self> {
+        Ok(f64::from_bits(read_float!(Reader(r), u64)))
+    }
+}
@@ -1,5 +1,6 @@
 mod compound;
 mod enumerate;
+mod float;
 mod other;
 mod scalar;
 mod wrapper;
@@ -1,5 +1,5 @@
 use crate::*;
-use std::ops::{Range, RangeInclusive};
+use core::ops::{Range, RangeInclusive};
 
 impl<T: Encode> Encode for Range<T> {
     #[inline]
@@ -7,13 +7,34 @@ impl<T: Encode> Encode for Range<T> {
         self.start.encode::<CONFIG>(c)?;
         self.end.encode::<CONFIG>(c)
     }
+    #[inline]
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        self.start.encoded_len::<CONFIG>() + self.end.encoded_len::<CONFIG>()
+    }
+}
+
+impl<T: MaxEncodedSize<CONFIG>, const CONFIG: u16> MaxEncodedSize<CONFIG> for Range<T> {
+    const MAX_ENCODED_SIZE: Option<usize> = size::mul(T::MAX_ENCODED_SIZE, 2);
 }
 
 impl<'de, T: Decode<'de>> Decode<'de> for Range<T> {
+    utils::decode_unlimited!('de);
+    #[inline]
+    fn decode_limited<const CONFIG: u16>(c: &mut &'de [u8], budget: &mut Budget) -> Result<Self> {
+        let start = T::decode_limited::<CONFIG>(c, budget)?;
+        let end = T::decode_limited::<CONFIG>(c, budget)?;
+        Ok(start..end)
+    }
+}
+
+impl<T: DecodeFromReader> DecodeFromReader for Range<T> {
     #[inline]
-    fn decode<const CONFIG: u16>(c: &mut &'de [u8]) -> Result<Self> {
-        let start = T::decode::<CONFIG>(c)?;
-        let end = T::decode::<CONFIG>(c)?;
+    fn decode_from_reader_limited<const CONFIG: u16>(
+        r: &mut (impl Read + ?Sized),
+        budget: &mut Budget,
+    ) -> Result<Self> {
+        let start = T::decode_from_reader_limited::<CONFIG>(r, budget)?;
+        let end = T::decode_from_reader_limited::<CONFIG>(r, budget)?;
         Ok(start..end)
     }
 }
@@ -24,13 +45,34 @@ impl<T: Encode> Encode for RangeInclusive<T> {
         self.start().encode::<CONFIG>(c)?;
         self.end().encode::<CONFIG>(c)
     }
+    #[inline]
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        self.start().encoded_len::<CONFIG>() + self.end().encoded_len::<CONFIG>()
+    }
+}
+
+impl<T: MaxEncodedSize<CONFIG>, const CONFIG: u16> MaxEncodedSize<CONFIG> for RangeInclusive<T> {
+    const MAX_ENCODED_SIZE: Option<usize> = size::mul(T::MAX_ENCODED_SIZE, 2);
 }
 
 impl<'de, T: Decode<'de>> Decode<'de> for RangeInclusive<T> {
+    utils::decode_unlimited!('de);
+    #[inline]
+    fn decode_limited<const CONFIG: u16>(c: &mut &'de [u8], budget: &mut Budget) -> Result<Self> {
+        let start = T::decode_limited::<CONFIG>(c, budget)?;
+        let end = T::decode_limited::<CONFIG>(c, budget)?;
+        Ok(start..=end)
+    }
+}
+
+impl<T: DecodeFromReader> DecodeFromReader for RangeInclusive<T> {
     #[inline]
-    fn decode<const CONFIG: u16>(c: &mut &'de [u8]) -> Result<Self> {
-        let start = T::decode::<CONFIG>(c)?;
-        let end = T::decode::<CONFIG>(c)?;
+    fn decode_from_reader_limited<const CONFIG: u16>(
+        r: &mut (impl Read + ?Sized),
+        budget: &mut Budget,
+    ) -> Result<Self> {
+        let start = T::decode_from_reader_limited::<CONFIG>(r, budget)?;
+        let end = T::decode_from_reader_limited::<CONFIG>(r, budget)?;
         Ok(start..=end)
     }
 }
@@ -1,35 +1,125 @@
 use crate::*;
-use std::mem::size_of;
+use core::mem::size_of;
+use utils::{Input, Reader};
 
 impl Encode for bool {
     #[inline]
     fn encode<const CONFIG: u16>(&self, writer: &mut (impl Write + ?Sized)) -> io::Result<()> {
         writer.write_all(&[*self as u8])
     }
+    #[inline]
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        1
+    }
+}
+
+impl<const CONFIG: u16> MaxEncodedSize<CONFIG> for bool {
+    const MAX_ENCODED_SIZE: Option<usize> = Some(1);
 }
 
 impl Decode<'_> for bool {
     #[inline]
     fn decode<const CONFIG: u16>(c: &mut &[u8]) -> Result<Self> {
-        match u8::decode::<CONFIG>(c)? {
-            0 => Ok(false),
-            1 => Ok(true),
-            _ => Err(Box::new(error::InvalidBoolValue)),
-        }
+        decode_bool(c.take_byte()?)
+    }
+}
+
+impl DecodeFromReader for bool {
+    #[inline]
+    fn decode_from_reader_limited<const CONFIG: u16>(
+        r: &mut (impl Read + ?Sized),
+        _: &mut Budget,
+    ) -> Result<Self> {
+        decode_bool(Reader(r).take_byte()?)
+    }
+}
+
+#[inline]
+fn decode_bool(byte: u8) -> Result<bool> {
+    match byte {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(Error::new(error::ErrorKind::InvalidBoolValue(byte))),
     }
 }
 
 impl Encode for char {
     #[inline]
     fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
-        u32::from(*self).encode::<CONFIG>(c)
+        match CONFIG & config::char_codec::GET {
+            config::char_codec::UTF8 => c.write_all(self.encode_utf8(&mut [0; 4]).as_bytes()),
+            _ => u32::from(*self).encode::<CONFIG>(c),
+        }
+    }
+    #[inline]
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        match CONFIG & config::char_codec::GET {
+            config::char_codec::UTF8 => self.len_utf8(),
+            _ => u32::from(*self).encoded_len::<CONFIG>(),
+        }
     }
 }
+impl<const CONFIG: u16> MaxEncodedSize<CONFIG> for char {
+    const MAX_ENCODED_SIZE: Option<usize> = match CONFIG & config::char_codec::GET {
+        config::char_codec::UTF8 => Some(4),
+        _ => <u32 as MaxEncodedSize<CONFIG>>::MAX_ENCODED_SIZE,
+    };
+}
 impl Decode<'_> for char {
     #[inline]
     fn decode<const CONFIG: u16>(c: &mut &[u8]) -> Result<Self> {
-        let num = u32::decode::<CONFIG>(c)?;
-        char::from_u32(num).ok_or_else(|| Error::from(error::InvalidChar))
+        match CONFIG & config::char_codec::GET {
+            config::char_codec::UTF8 => decode_utf8(c),
+            _ => decode_char(u32::decode::<CONFIG>(c)?),
+        }
+    }
+}
+impl DecodeFromReader for char {
+    #[inline]
+    fn decode_from_reader_limited<const CONFIG: u16>(
+        r: &mut (impl Read + ?Sized),
+        _: &mut Budget,
+    ) -> Result<Self> {
+        match CONFIG & config::char_codec::GET {
+            config::char_codec::UTF8 => decode_utf8(&mut Reader(r)),
+            _ => decode_char(u32::decode_from_reader::<CONFIG>(r)?),
+        }
+    }
+}
+
+#[inline]
+fn decode_char(num: u32) -> Result<char> {
+    char::from_u32(num).ok_or_else(|| Error::new(error::ErrorKind::InvalidChar(num)))
+}
+
+/// Decodes a UTF-8 sequence, See [config::char_codec::UTF8]
+fn decode_utf8(c: &mut impl Input) -> Result<char> {
+    let mut buf = [c.take_byte()?, 0, 0, 0];
+    let len = match buf[0] {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF7 => 4,
+        _ => 0,
+    };
+    // Malformed sequence is reported with the bytes read so far, In big endian order.
+    let invalid = |buf: &[u8]| {
+        let num = buf.iter().fold(0, |num, &byte| num << 8 | byte as u32);
+        Error::new(error::ErrorKind::InvalidChar(num))
+    };
+    if len == 0 {
+        return Err(invalid(&buf[..1]));
+    }
+    for i in 1..len {
+        buf[i] = c.take_byte()?;
+        if buf[i] & 0xC0 != 0x80 {
+            return Err(invalid(&buf[..=i]));
+        }
+    }
+    // Rejects overlong, Surrogate and out of range sequences.
+    match core::str::from_utf8(&buf[..len]) {
+        Ok(s) => Ok(s.chars().next().unwrap()),
+        Err(_) => Err(invalid(&buf[..len])),
     }
 }
 
@@ -40,6 +130,14 @@ impl Encode for u8 {
     fn encode<const CONFIG: u16>(&self, writer: &mut (impl Write + ?Sized)) -> io::Result<()> {
         writer.write_all(&[*self])
     }
+    #[inline]
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        1
+    }
+}
+
+impl<const CONFIG: u16> MaxEncodedSize<CONFIG> for u8 {
+    const MAX_ENCODED_SIZE: Option<usize> = Some(1);
 }
 
 impl Decode<'_> for u8 {
@@ -52,49 +150,132 @@ impl Decode<'_> for u8 {
                 Ok(*byte)
             }
         } else {
-            Err(Box::new(error::InsufficientBytes))
+            Err(Error::insufficient_bytes(1))
         }
     }
 }
 
-impl Encode for i8 {
+impl DecodeFromReader for u8 {
     #[inline]
-    fn encode<const CONFIG: u16>(&self, writer: &mut (impl Write + ?Sized)) -> io::Result<()> {
-        writer.write_all(&[*self as u8])
+    fn decode_from_reader_limited<const CONFIG: u16>(
+        r: &mut (impl Read + ?Sized),
+        _: &mut Budget,
+    ) -> Result<Self> {
+        Reader(r).take_byte()
     }
 }
-impl Decode<'_> for i8 {
-    #[inline]
-    fn decode<const CONFIG: u16>(c: &mut &[u8]) -> Result<Self> {
-        u8::decode::<CONFIG>(c).map(|byte| byte as i8)
+
+// -----------------------------------------------------------------------------------
+
+/// Maps a signed integer of `bits` width to unsigned, See [config::int_codec]
+#[inline]
+fn encode_int<const CONFIG: u16>(num: i128, bits: u32) -> io::Result<u128> {
+    match CONFIG & config::int_codec::GET {
+        config::int_codec::ZIG_ZAG => Ok(((num << 1) ^ (num >> 127)) as u128),
+        config::int_codec::SIGN_BIT => match num.unsigned_abs().checked_mul(2) {
+            Some(mag) => Ok(mag | (num < 0) as u128),
+            None => Err(utils::invalid_input(error::IntegerOverflow)),
+        },
+        _ => Ok(num as u128 & (u128::MAX >> (u128::BITS - bits))),
     }
 }
 
-// -----------------------------------------------------------------------------------
+/// Reverse of [encode_int]
+#[inline]
+fn decode_int<const CONFIG: u16>(num: u128, bits: u32) -> Result<i128> {
+    match CONFIG & config::int_codec::GET {
+        config::int_codec::ZIG_ZAG => Ok(((num >> 1) as i128) ^ -((num & 1) as i128)),
+        config::int_codec::SIGN_BIT => {
+            let mag = num >> 1;
+            match num & 1 {
+                0 => Ok(i128::try_from(mag)?),
+                _ => 0_i128
+                    .checked_sub_unsigned(mag)
+                    .ok_or(Error::from(error::IntegerOverflow)),
+            }
+        }
+        _ => {
+            if num >> (bits - 1) >> 1 != 0 {
+                return Err(Error::from(error::IntegerOverflow));
+            }
+            let shift = u128::BITS - bits;
+            Ok(((num << shift) as i128) >> shift)
+        }
+    }
+}
 
-#[rustfmt::skip]
-macro_rules! decode_zigzag {
-    (signed, $num:tt) => { $num };
-    (unsigned, $num:tt) => { (($num >> 1) as Self) ^ -(($num & 1) as Self) };
+/// Maximum number of bits of a signed integer of `bits` width, Once mapped by [encode_int]
+const fn int_bits<const CONFIG: u16>(bits: u32) -> u32 {
+    match CONFIG & config::int_codec::GET {
+        config::int_codec::SIGN_BIT if bits < u128::BITS => bits + 1,
+        _ => bits,
+    }
 }
 
-#[rustfmt::skip]
-macro_rules! int_to_uint {
-    (i16) => { u16 };
-    (i32) => { u32 };
-    (i64) => { u64 };
-    (i128) => { u128 };
-    (isize) => { usize };
+/// Signed LEB128, Used with [config::int_codec::TWOS_COMPLEMENT]
+mod sleb128 {
+    use super::*;
+
+    pub fn encode(mut num: i128, writer: &mut (impl Write + ?Sized)) -> io::Result<()> {
+        loop {
+            let byte = num as u8 & 0b0111_1111;
+            num >>= 7;
+            if (num == 0 && byte & 0b0100_0000 == 0) || (num == -1 && byte & 0b0100_0000 != 0) {
+                return writer.write_all(&[byte]);
+            }
+            writer.write_all(&[byte | 0b1000_0000])?;
+        }
+    }
+
+    pub fn len(num: i128) -> usize {
+        // Significant bits, plus the sign bit.
+        let bits = i128::BITS - if num < 0 { !num } else { num }.leading_zeros() + 1;
+        size::leb128(bits)
+    }
+
+    pub fn decode(c: &mut impl Input) -> Result<i128> {
+        let mut num: i128 = 0;
+        let mut shift = 0;
+        loop {
+            let byte = c.take_byte()?;
+            if shift == 126 {
+                // Only 2 bits are left, The rest must be sign extension.
+                let rest = byte >> 1;
+                if byte & 0b1000_0000 != 0 || (rest != 0 && rest != 0b0011_1111) {
+                    return Err(Error::from(error::IntegerOverflow));
+                }
+            }
+            num |= ((byte & 0b0111_1111) as i128) << shift;
+            shift += 7;
+            if byte & 0b1000_0000 == 0 {
+                if shift < i128::BITS && byte & 0b0100_0000 != 0 {
+                    num |= -1 << shift;
+                }
+                return Ok(num);
+            }
+        }
+    }
 }
 
 #[rustfmt::skip]
 macro_rules! leb128 {
-    (@encode: float, $self:tt as $ty:tt, $writer:tt) => { $writer.write_all(&$self.to_le_bytes()) };
     (@encode: signed, $self:tt as $ty:tt, $writer:tt) => { 
         leb128!(encode_signed_or_unsigned($writer, *$self)) 
     };
     (@encode: unsigned, $self:tt as $ty:tt, $writer:tt) => {
-        leb128!(encode_signed_or_unsigned($writer, (($self << 1) ^ ($self >> Self::BITS - 1)) as int_to_uint!($ty))) 
+        match CONFIG & config::int_codec::GET {
+            config::int_codec::TWOS_COMPLEMENT => sleb128::encode(*$self as i128, $writer),
+            _ => leb128!(encode_signed_or_unsigned($writer, encode_int::<CONFIG>(*$self as i128, Self::BITS)?)),
+        }
+    };
+    (@max_size: signed) => { size::leb128(Self::BITS) };
+    (@max_size: unsigned) => { size::leb128(int_bits::<CONFIG>(Self::BITS)) };
+    (@len: signed, $self:tt as $ty:tt) => { utils::leb128_len(*$self as u128) };
+    (@len: unsigned, $self:tt as $ty:tt) => {
+        match CONFIG & config::int_codec::GET {
+            config::int_codec::TWOS_COMPLEMENT => sleb128::len(*$self as i128),
+            _ => utils::leb128_len(encode_int::<CONFIG>(*$self as i128, Self::BITS).unwrap_or(u128::MAX)),
+        }
     };
     (encode_signed_or_unsigned($writer:tt, $num: expr)) => ({
         let mut num = $num;
@@ -105,32 +286,84 @@ macro_rules! leb128 {
         $writer.write_all(&[num as u8])
     });
 
-    (@decode: float, $ty:tt, $c:tt) => { Self::from_le_bytes(*<&[u8; size_of::<Self>()]>::decode::<CONFIG>($c)?) };
-    (@decode: signed, $ty:tt, $c:tt) => { leb128!(decode_signed_or_unsigned(signed, $ty, $c)) };
-    (@decode: unsigned, $ty:tt, $c:tt) => { leb128!(decode_signed_or_unsigned(unsigned, int_to_uint!($ty), $c)) };
-    (decode_signed_or_unsigned($catagory:tt, $ty:ty, $c:tt)) => ({
+    (@decode: signed, $ty:tt, $c:tt) => { leb128!(decode_signed_or_unsigned($ty, $c)) };
+    (@decode: unsigned, $ty:tt, $c:tt) => {
+        Self::try_from(match CONFIG & config::int_codec::GET {
+            config::int_codec::TWOS_COMPLEMENT => sleb128::decode($c)?,
+            _ => decode_int::<CONFIG>(leb128!(decode_signed_or_unsigned(u128, $c)), Self::BITS)?,
+        })?
+    };
+    (decode_signed_or_unsigned($ty:ty, $c:tt)) => ({
         let mut shift: u8 = 0;
         let mut num = 0;
         loop {
-            let byte = u8::decode::<CONFIG>($c)?;
-            if match Self::BITS {
+            let byte = $c.take_byte()?;
+            if match <$ty>::BITS {
                 16  => shift == 14  && byte > 0b11,
                 32  => shift == 28  && byte > 0b1111,
                 64  => shift == 63  && byte > 0b1,
                 128 => shift == 126 && byte > 0b11,
                 _ => unreachable!()
             } {
-                return Err(Box::new(error::IntegerOverflow));
+                return Err(Error::from(error::IntegerOverflow));
             }
             num |= ((byte & 0b0111_1111) as $ty) << shift;
             if (byte & 0b1000_0000) == 0 {
-                break decode_zigzag!($catagory, num);
+                break num;
             }
             shift += 7;
         }
     });
 }
 
+/// Encoding with the types of [var_int] module.
+#[rustfmt::skip]
+macro_rules! varint {
+    (@encode: signed, $self:tt as $ty:tt, $writer:tt, $var:ident) => { varint!(encode_signed_or_unsigned($writer, *$self, $var)) };
+    (@encode: unsigned, $self:tt as $ty:tt, $writer:tt, $var:ident) => {
+        varint!(encode_signed_or_unsigned($writer, encode_int::<CONFIG>(*$self as i128, Self::BITS)?, $var))
+    };
+    (encode_signed_or_unsigned($writer:tt, $num: expr, $var:ident)) => {
+        match u64::try_from($num) {
+            Ok(num) if num <= var_int::$var::MAX => var_int::$var(num).encode::<CONFIG>($writer),
+            _ => Err(utils::invalid_input(error::IntegerOverflow)),
+        }
+    };
+    (@max_size: signed, $var:ident) => { varint!(max_size(Self::BITS, $var)) };
+    (@max_size: unsigned, $var:ident) => { varint!(max_size(int_bits::<CONFIG>(Self::BITS), $var)) };
+    (max_size($bits: expr, $var:ident)) => {
+        var_int::$var::len_of(if $bits >= u64::BITS { u64::MAX } else { (1 << $bits) - 1 })
+    };
+    (@len: signed, $self:tt as $ty:tt, $var:ident) => { varint!(len_of(*$self, $var)) };
+    (@len: unsigned, $self:tt as $ty:tt, $var:ident) => {
+        varint!(len_of(encode_int::<CONFIG>(*$self as i128, Self::BITS).unwrap_or(u128::MAX), $var))
+    };
+    (len_of($num: expr, $var:ident)) => {
+        var_int::$var::len_of(u64::try_from($num).unwrap_or(u64::MAX))
+    };
+    (@decode: signed, $ty:tt, $c:tt, $var:ident) => { Self::try_from(var_int::$var::decode_from($c)?.0)? };
+    (@decode: unsigned, $ty:tt, $c:tt, $var:ident) => {
+        Self::try_from(decode_int::<CONFIG>(var_int::$var::decode_from($c)?.0 as u128, Self::BITS)?)?
+    };
+}
+
+macro_rules! decode_num {
+    ($catagory:tt, $num:tt, $c:tt) => {
+        Ok(match CONFIG & config::num::GET {
+            config::num::LE => Self::from_le_bytes($c.take_array()?),
+            config::num::BE => Self::from_be_bytes($c.take_array()?),
+            config::num::NE => Self::from_ne_bytes($c.take_array()?),
+            config::num::LEB128 => leb128!(@decode: $catagory, $num, $c),
+            config::num::BEU62 => varint!(@decode: $catagory, $num, $c, BEU62),
+            config::num::VLQ => varint!(@decode: $catagory, $num, $c, VLQ),
+            config::num::COMPACT_SIZE => varint!(@decode: $catagory, $num, $c, CompactSize),
+            config::num::SQLITE_VARINT => varint!(@decode: $catagory, $num, $c, SqliteVarint),
+            config::num::PREFIX_VARINT => varint!(@decode: $catagory, $num, $c, PrefixVarint),
+            _ => unreachable!()
+        })
+    };
+}
+
 macro_rules! impl_data_type_for {
     [$catagory:tt => $($num:tt)*] => ($(
         impl Encode for $num {
@@ -140,23 +373,48 @@ macro_rules! impl_data_type_for {
                     config::num::BE => writer.write_all(&self.to_be_bytes()),
                     config::num::NE => writer.write_all(&self.to_ne_bytes()),
                     config::num::LEB128 => leb128!(@encode: $catagory, self as $num, writer),
+                    config::num::BEU62 => varint!(@encode: $catagory, self as $num, writer, BEU62),
+                    config::num::VLQ => varint!(@encode: $catagory, self as $num, writer, VLQ),
+                    config::num::COMPACT_SIZE => varint!(@encode: $catagory, self as $num, writer, CompactSize),
+                    config::num::SQLITE_VARINT => varint!(@encode: $catagory, self as $num, writer, SqliteVarint),
+                    config::num::PREFIX_VARINT => varint!(@encode: $catagory, self as $num, writer, PrefixVarint),
                     _ => unreachable!()
                 }
             }
+            fn encoded_len<const CONFIG: u16>(&self) -> usize {
+                match CONFIG & config::num::GET {
+                    config::num::LEB128 => leb128!(@len: $catagory, self as $num),
+                    config::num::BEU62 => varint!(@len: $catagory, self as $num, BEU62),
+                    config::num::VLQ => varint!(@len: $catagory, self as $num, VLQ),
+                    config::num::COMPACT_SIZE => varint!(@len: $catagory, self as $num, CompactSize),
+                    config::num::SQLITE_VARINT => varint!(@len: $catagory, self as $num, SqliteVarint),
+                    config::num::PREFIX_VARINT => varint!(@len: $catagory, self as $num, PrefixVarint),
+                    _ => size_of::<Self>(),
+                }
+            }
+        }
+        impl<const CONFIG: u16> MaxEncodedSize<CONFIG> for $num {
+            const MAX_ENCODED_SIZE: Option<usize> = Some(match CONFIG & config::num::GET {
+                config::num::LEB128 => leb128!(@max_size: $catagory),
+                config::num::BEU62 => varint!(@max_size: $catagory, BEU62),
+                config::num::VLQ => varint!(@max_size: $catagory, VLQ),
+                config::num::COMPACT_SIZE => varint!(@max_size: $catagory, CompactSize),
+                config::num::SQLITE_VARINT => varint!(@max_size: $catagory, SqliteVarint),
+                config::num::PREFIX_VARINT => varint!(@max_size: $catagory, PrefixVarint),
+                _ => size_of::<Self>(),
+            });
         }
         impl Decode<'_> for $num {
             fn decode<const CONFIG: u16>(c: &mut &[u8]) -> Result<Self> {
-                Ok(match CONFIG & config::num::GET {
-                    config::num::LE => Self::from_le_bytes(*<&[u8; size_of::<Self>()]>::decode::<CONFIG>(c)?),
-                    config::num::BE => Self::from_be_bytes(*<&[u8; size_of::<Self>()]>::decode::<CONFIG>(c)?),
-                    config::num::NE => Self::from_ne_bytes(*<&[u8; size_of::<Self>()]>::decode::<CONFIG>(c)?),
-                    config::num::LEB128 => leb128!(@decode: $catagory, $num, c),
-                    _ => unreachable!()
-                })
+                decode_num!($catagory, $num, c)
+            }
+        }
+        impl DecodeFromReader for $num {
+            fn decode_from_reader_limited<const CONFIG: u16>(r: &mut (impl Read + ?Sized), _: &mut Budget) -> Result<Self> {
+                decode_num!($catagory, $num, (&mut Reader(r)))
             }
         }
     )*);
 }
 impl_data_type_for!(signed => u16 u32 u64 u128 usize);
-impl_data_type_for!(unsigned => i16 i32 i64 i128 isize);
-impl_data_type_for!(float => f32 f64);
+impl_data_type_for!(unsigned => i8 i16 i32 i64 i128 isize);
@@ -1,56 +1,101 @@
 use crate::*;
-use std::{
-    borrow::Cow,
-    cell::{Cell, RefCell},
+use alloc::{
+    borrow::{Cow, ToOwned},
     rc::Rc,
     sync::Arc,
 };
+use core::cell::{Cell, RefCell};
 
 macro_rules! impls {
     [Encode for $($name:ty),*] => ($(
         impl<T: Encode + ?Sized> Encode for $name {
             #[inline]
             fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> { (**self).encode::<CONFIG>(c) }
+            #[inline]
+            fn encoded_len<const CONFIG: u16>(&self) -> usize { (**self).encoded_len::<CONFIG>() }
+        }
+    )*);
+    [MaxEncodedSize for $($name:ty),*] => ($(
+        impl<T: MaxEncodedSize<CONFIG> + ?Sized, const CONFIG: u16> MaxEncodedSize<CONFIG> for $name {
+            const MAX_ENCODED_SIZE: Option<usize> = T::MAX_ENCODED_SIZE;
         }
     )*);
     [Decode for $($name:ident),*] => ($(
         impl<'de, T: Decode<'de>> Decode<'de> for $name<T> {
+            utils::decode_unlimited!('de);
             #[inline]
-            fn decode<const CONFIG: u16>(c: &mut &'de [u8]) -> Result<Self> { T::decode::<CONFIG>(c).map(Self::from) }
+            fn decode_limited<const CONFIG: u16>(c: &mut &'de [u8], budget: &mut Budget) -> Result<Self> { T::decode_limited::<CONFIG>(c, budget).map(Self::from) }
+        }
+        impl<T: DecodeFromReader> DecodeFromReader for $name<T> {
+            #[inline]
+            fn decode_from_reader_limited<const CONFIG: u16>(r: &mut (impl Read + ?Sized), budget: &mut Budget) -> Result<Self> {
+                T::decode_from_reader_limited::<CONFIG>(r, budget).map(Self::from)
+            }
         }
     )*);
 }
 
 impls!(Encode for &T, &mut T, Box<T>, Rc<T>, Arc<T>);
 impls!(Decode for Box, Rc, Arc, Cell, RefCell);
+impls!(MaxEncodedSize for &T, &mut T, Box<T>, Rc<T>, Arc<T>, Cell<T>, RefCell<T>);
 
 macro_rules! impl_sp {
     [$($name: ident),*] => ($(
         impl<'de> Decode<'de> for $name<str> {
-            #[inline] fn decode<const CONFIG: u16>(c: &mut &'de [u8]) -> Result<Self> {
-                <&'de str>::decode::<CONFIG>(c).map(Self::from)
+            utils::decode_unlimited!('de);
+            #[inline] fn decode_limited<const CONFIG: u16>(c: &mut &'de [u8], budget: &mut Budget) -> Result<Self> {
+                <&'de str>::decode_limited::<CONFIG>(c, budget).map(Self::from)
+            }
+        }
+        impl DecodeFromReader for $name<str> {
+            #[inline] fn decode_from_reader_limited<const CONFIG: u16>(r: &mut (impl Read + ?Sized), budget: &mut Budget) -> Result<Self> {
+                String::decode_from_reader_limited::<CONFIG>(r, budget).map(Self::from)
             }
         }
         impl<'de, T: Decode<'de>> Decode<'de> for $name<[T]> {
-            #[inline] fn decode<const CONFIG: u16>(c: &mut &'de [u8]) -> Result<Self> {
-                Vec::<T>::decode::<CONFIG>(c).map(Self::from)
+            utils::decode_unlimited!('de);
+            #[inline] fn decode_limited<const CONFIG: u16>(c: &mut &'de [u8], budget: &mut Budget) -> Result<Self> {
+                Vec::<T>::decode_limited::<CONFIG>(c, budget).map(Self::from)
+            }
+        }
+        impl<T: DecodeFromReader> DecodeFromReader for $name<[T]> {
+            #[inline] fn decode_from_reader_limited<const CONFIG: u16>(r: &mut (impl Read + ?Sized), budget: &mut Budget) -> Result<Self> {
+                Vec::<T>::decode_from_reader_limited::<CONFIG>(r, budget).map(Self::from)
             }
         }
     )*);
 }
 impl_sp!(Box, Rc, Arc);
 
-impl<T> Encode for std::marker::PhantomData<T> {
+impl<T> Encode for core::marker::PhantomData<T> {
     #[inline]
     fn encode<const CONFIG: u16>(&self, _: &mut (impl Write + ?Sized)) -> io::Result<()> {
         Ok(())
     }
+    #[inline]
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        0
+    }
+}
+
+impl<T, const CONFIG: u16> MaxEncodedSize<CONFIG> for core::marker::PhantomData<T> {
+    const MAX_ENCODED_SIZE: Option<usize> = Some(0);
 }
 
-impl<T> Decode<'_> for std::marker::PhantomData<T> {
+impl<T> Decode<'_> for core::marker::PhantomData<T> {
     #[inline]
     fn decode<const CONFIG: u16>(_: &mut &[u8]) -> Result<Self> {
-        Ok(std::marker::PhantomData)
+        Ok(core::marker::PhantomData)
+    }
+}
+
+impl<T> DecodeFromReader for core::marker::PhantomData<T> {
+    #[inline]
+    fn decode_from_reader_limited<const CONFIG: u16>(
+        _: &mut (impl Read + ?Sized),
+        _: &mut Budget,
+    ) -> Result<Self> {
+        Ok(core::marker::PhantomData)
     }
 }
 
@@ -59,6 +104,10 @@ impl<T: Encode + Copy> Encode for Cell<T> {
     fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
         self.get().encode::<CONFIG>(c)
     }
+    #[inline]
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        self.get().encoded_len::<CONFIG>()
+    }
 }
 
 impl<T: Encode> Encode for RefCell<T> {
@@ -68,6 +117,11 @@ impl<T: Encode> Encode for RefCell<T> {
             .map_err(utils::invalid_input)?
             .encode::<CONFIG>(c)
     }
+    #[inline]
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        self.try_borrow()
+            .map_or(0, |val| val.encoded_len::<CONFIG>())
+    }
 }
 
 impl<'a, T> Encode for Cow<'a, T>
@@ -78,15 +132,41 @@ where
     fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
         (**self).encode::<CONFIG>(c)
     }
+    #[inline]
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        (**self).encoded_len::<CONFIG>()
+    }
+}
+
+impl<T, const CONFIG: u16> MaxEncodedSize<CONFIG> for Cow<'_, T>
+where
+    T: ?Sized + MaxEncodedSize<CONFIG> + ToOwned,
+{
+    const MAX_ENCODED_SIZE: Option<usize> = T::MAX_ENCODED_SIZE;
 }
 
 impl<'de, 'a, T: ?Sized> Decode<'de> for Cow<'a, T>
 where
     T: ToOwned,
     T::Owned: Decode<'de>,
+{
+    utils::decode_unlimited!('de);
+    #[inline]
+    fn decode_limited<const CONFIG: u16>(c: &mut &'de [u8], budget: &mut Budget) -> Result<Self> {
+        T::Owned::decode_limited::<CONFIG>(c, budget).map(Cow::Owned)
+    }
+}
+
+impl<T: ?Sized> DecodeFromReader for Cow<'_, T>
+where
+    T: ToOwned,
+    T::Owned: DecodeFromReader,
 {
     #[inline]
-    fn decode<const CONFIG: u16>(c: &mut &'de [u8]) -> Result<Self> {
-        T::Owned::decode::<CONFIG>(c).map(Cow::Owned)
+    fn decode_from_reader_limited<const CONFIG: u16>(
+        r: &mut (impl Read + ?Sized),
+        budget: &mut Budget,
+    ) -> Result<Self> {
+        T::Owned::decode_from_reader_limited::<CONFIG>(r, budget).map(Cow::Owned)
     }
 }
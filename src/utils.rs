@@ -1,8 +1,8 @@
 use crate::*;
-use std::iter::FromIterator;
+use core::iter::FromIterator;
 
 #[inline]
-pub fn invalid_input(error: impl Into<Error>) -> io::Error {
+pub fn invalid_input(error: impl Into<Box<dyn core::error::Error + Send + Sync>>) -> io::Error {
     io::Error::new(io::ErrorKind::InvalidInput, error)
 }
 
@@ -15,22 +15,125 @@ pub fn get_slice<'de>(remaining: &mut &'de [u8], len: usize) -> Result<&'de [u8]
             Ok(slice)
         }
     } else {
-        Err(Box::new(error::InsufficientBytes))
+        Err(Error::insufficient_bytes(len - remaining.len()))
+    }
+}
+
+/// A [Write] sink that only counts the bytes written to it.
+pub struct Counter(pub usize);
+
+impl Write for Counter {
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.0 += buf.len();
+        Ok(())
+    }
+}
+
+/// Number of bytes required to encode `num` in [LEB128](crate::config::num::LEB128) format.
+#[inline]
+pub fn leb128_len(num: u128) -> usize {
+    let bits = (u128::BITS - num.leading_zeros()) as usize;
+    bits.div_ceil(7).max(1)
+}
+
+/// Source of bytes, shared by slice and [Read] based decoders.
+pub trait Input {
+    fn take_byte(&mut self) -> Result<u8>;
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N]>;
+}
+
+impl Input for &[u8] {
+    #[inline]
+    fn take_byte(&mut self) -> Result<u8> {
+        u8::decode::<0>(self)
+    }
+    #[inline]
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        <&[u8; N]>::decode::<0>(self).copied()
+    }
+}
+
+/// Adapts any [Read] into an [Input].
+pub struct Reader<'r, R: ?Sized>(pub &'r mut R);
+
+impl<R: Read + ?Sized> Input for Reader<'_, R> {
+    #[inline]
+    fn take_byte(&mut self) -> Result<u8> {
+        self.take_array().map(|[byte]| byte)
+    }
+    #[inline]
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut buf = [0; N];
+        read_exact(self.0, &mut buf)?;
+        Ok(buf)
     }
 }
 
 #[inline]
-pub fn try_collect<'de, T, I, const CONFIG: u16>(cursor: &mut &'de [u8], len: usize) -> Result<I>
+pub fn read_exact(reader: &mut (impl Read + ?Sized), buf: &mut [u8]) -> Result<()> {
+    reader.read_exact(buf).map_err(|err| match err.kind() {
+        io::ErrorKind::UnexpectedEof => Error::from(error::InsufficientBytes),
+        _ => Error::from(err),
+    })
+}
+
+/// Read `len` bytes from `reader`.
+///
+/// The buffer grows as bytes arrive, So a bogus length can't make us allocate upfront.
+pub fn read_bytes(reader: &mut (impl Read + ?Sized), len: usize) -> Result<Vec<u8>> {
+    const CHUNK: usize = 8 * 1024;
+    let mut bytes = Vec::with_capacity(len.min(CHUNK));
+    while bytes.len() < len {
+        let start = bytes.len();
+        bytes.resize(start + (len - start).min(CHUNK), 0);
+        read_exact(reader, &mut bytes[start..])?;
+    }
+    Ok(bytes)
+}
+
+#[inline]
+pub fn try_collect<'de, T, I, const CONFIG: u16>(
+    cursor: &mut &'de [u8],
+    len: usize,
+    budget: &mut Budget,
+) -> Result<I>
 where
     T: Decode<'de>,
     I: FromIterator<T>,
+{
+    budget.collection::<T>(len)?;
+    collect(len, || T::decode_limited::<CONFIG>(cursor, budget))
+}
+
+#[inline]
+pub fn try_collect_from_reader<T, I, const CONFIG: u16>(
+    reader: &mut (impl Read + ?Sized),
+    len: usize,
+    budget: &mut Budget,
+) -> Result<I>
+where
+    T: DecodeFromReader,
+    I: FromIterator<T>,
+{
+    budget.collection::<T>(len)?;
+    collect(len, || {
+        T::decode_from_reader_limited::<CONFIG>(reader, budget)
+    })
+}
+
+/// Collect `len` items, Without checking the [Limits].
+#[inline]
+pub fn collect<T, I>(len: usize, decode: impl FnMut() -> Result<T>) -> Result<I>
+where
+    I: FromIterator<T>,
 {
     let mut error = None;
-    let out = I::from_iter(Iter::<T, CONFIG> {
+    let out = I::from_iter(Iter {
+        idx: 0,
         len,
         err: &mut error,
-        reader: cursor,
-        _marker: std::marker::PhantomData,
+        decode,
     });
     match error {
         Some(err) => Err(err),
@@ -38,16 +141,16 @@ where
     }
 }
 
-pub struct Iter<'err, 'cursor, 'de, T, const CONFIG: u16> {
+pub struct Iter<'err, F> {
+    idx: usize,
     len: usize,
     err: &'err mut Option<Error>,
-    reader: &'cursor mut &'de [u8],
-    _marker: std::marker::PhantomData<T>,
+    decode: F,
 }
 
-impl<'err, 'cursor, 'de, T, const CONFIG: u16> Iterator for Iter<'err, 'cursor, 'de, T, CONFIG>
+impl<T, F> Iterator for Iter<'_, F>
 where
-    T: Decode<'de>,
+    F: FnMut() -> Result<T>,
 {
     type Item = T;
     #[inline]
@@ -55,21 +158,35 @@ where
         if self.len == 0 {
             return None;
         }
-        match T::decode::<CONFIG>(self.reader) {
+        match (self.decode)() {
             Ok(val) => {
+                self.idx += 1;
                 self.len -= 1;
                 Some(val)
             }
             Err(err) => {
                 self.len = 0;
-                *self.err = Some(err);
+                *self.err = Some(err.index(self.idx));
                 None
             }
         }
     }
 
+    /// `len` is untrusted, So the lower bound is capped to avoid preallocating a huge buffer.
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.len, Some(self.len))
+        let cap = limits::MAX_PREALLOC / size_of::<T>().max(1);
+        (self.len.min(cap), Some(self.len))
     }
 }
+
+/// [Decode::decode] of the impls that override [Decode::decode_limited], It decodes without any [Limits].
+macro_rules! decode_unlimited {
+    ($de: lifetime) => {
+        #[inline]
+        fn decode<const CONFIG: u16>(c: &mut &$de [u8]) -> Result<Self> {
+            Self::decode_limited::<CONFIG>(c, &mut Budget::unlimited())
+        }
+    };
+}
+pub(crate) use decode_unlimited;
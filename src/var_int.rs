@@ -1,6 +1,8 @@
 //! #### Variable-Length Integer Encoding
 //!
-//! Support types are [BEU15], [BEU22], [BEU29], [BEU30]
+//! Support types are [BEU15], [BEU22], [BEU29], [BEU30], [BEU62],
+//! And varints of other formats: [VLQ], [CompactSize], [SqliteVarint], [PrefixVarint].
+//!
 //! By default, length of collections is represented with [BEU30].
 //!
 //! Encoding algorithm is very straightforward,
@@ -23,39 +25,51 @@
 //! ```
 
 use crate::*;
-use std::{
+use core::{
     convert::{Infallible, TryFrom},
     fmt,
 };
 
 macro_rules! def {
-    [$(#[$doc:meta])* $name:ident($ty:ty), BITS: $BITS:literal, UsizeTryFromErr: $err: ty, $encode:item, $decode:item] => {
+    [$(#[$doc:meta])* $name:ident($ty:ty), BITS: $BITS:literal, MAX_ENCODED_SIZE: $MAX_SIZE:literal, UsizeTryFromErr: $err: ty, $encode:item, $encoded_len:item, $decode:item] => {
         $(#[$doc])*
         #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
         pub struct $name(pub $ty);
         impl $name {
             /// The largest value that can be represented by this integer type.
-            pub const MAX: $ty = (1 << $BITS) - 1;
+            pub const MAX: $ty = <$ty>::MAX >> (<$ty>::BITS - $BITS);
             /// The smallest value that can be represented by this integer type.
             pub const MIN: $ty = 0;
             /// The size of this integer type in bits.
             pub const BITS: u32 = $BITS;
         }
-        impl Encode for $name { $encode }
-        impl Decode<'_> for $name { $decode }
+        impl $name { $decode }
+        impl Encode for $name { $encode $encoded_len }
+        impl<const CONFIG: u16> MaxEncodedSize<CONFIG> for $name {
+            const MAX_ENCODED_SIZE: Option<usize> = Some($MAX_SIZE);
+        }
+        impl Decode<'_> for $name {
+            #[inline] fn decode<const CONFIG: u16>(c: &mut &[u8]) -> Result<Self> { Self::decode_from(c) }
+        }
+        impl DecodeFromReader for $name {
+            #[inline] fn decode_from_reader_limited<const CONFIG: u16>(r: &mut (impl Read + ?Sized), _: &mut Budget) -> Result<Self> {
+                Self::decode_from(&mut utils::Reader(r))
+            }
+        }
         impl TryFrom<usize> for $name {
             type Error = error::IntegerOverflow;
-            #[inline] fn try_from(num: usize) -> std::result::Result<Self, Self::Error> {
-                if num > (1 << $BITS) - 1 {
+            #[inline] fn try_from(num: usize) -> core::result::Result<Self, Self::Error> {
+                if num as u128 > Self::MAX as u128 {
                     Err(error::IntegerOverflow)
                 } else {
                     Ok(Self(num as $ty))
                 }
             }
         }
+        #[allow(clippy::infallible_try_from)]
         impl TryFrom<$name> for usize {
             type Error = $err;
-            #[inline] fn try_from(num: $name) -> std::result::Result<Self, Self::Error> { TryFrom::try_from(num.0) }
+            #[inline] fn try_from(num: $name) -> core::result::Result<Self, Self::Error> { TryFrom::try_from(num.0) }
         }
         impl From<$ty> for $name { fn from(num: $ty) -> Self { Self(num) } }
         impl fmt::Display for $name {
@@ -82,6 +96,7 @@ def!(
     /// |   1   |   2    |     15      | 0..32768 |
     BEU15(u16),
     BITS: 15,
+    MAX_ENCODED_SIZE: 2,
     UsizeTryFromErr: Infallible,
     fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
         let num = self.0;
@@ -93,13 +108,16 @@ def!(
         // (1) 1111111 11111111
         c.write_all(&[0x80 | b1 , b2])
     },
-    fn decode<const CONFIG: u16>(c: &mut &[u8]) -> Result<Self> {
-        let b1 = u8::decode::<CONFIG>(c)? as u16;
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        if self.0 < (1 << 7) { 1 } else { 2 }
+    },
+    fn decode_from(c: &mut impl utils::Input) -> Result<Self> {
+        let b1 = c.take_byte()? as u16;
         // (0) 1111111
         if b1 >> 7 == 0 {
             return Ok(Self(b1))
         }
-        let b2 = u8::decode::<CONFIG>(c)? as u16;
+        let b2 = c.take_byte()? as u16;
         // (1) 1111111 11111111
         Ok(Self(((b1 & 0x7F) << 8) | b2))
     }
@@ -115,7 +133,8 @@ def!(
     /// |  11   |   3    |     22      | 0..4194304 |
     BEU22(u32),
     BITS: 22,
-    UsizeTryFromErr: std::num::TryFromIntError,
+    MAX_ENCODED_SIZE: 3,
+    UsizeTryFromErr: core::num::TryFromIntError,
     fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
         let num = self.0;
         let b3 = num as u8;
@@ -131,18 +150,25 @@ def!(
         let b1 = (num >> 16) as u8;
         c.write_all(&[0xC0 | b1, b2, b3])
     },
-    fn decode<const CONFIG: u16>(c: &mut &[u8]) -> Result<Self> {
-        let b1 = u8::decode::<CONFIG>(c)? as u32;
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        match self.0 {
+            0..=0x7F => 1,
+            0x80..=0x3FFF => 2,
+            _ => 3,
+        }
+    },
+    fn decode_from(c: &mut impl utils::Input) -> Result<Self> {
+        let b1 = c.take_byte()? as u32;
         // (0) 1111111
         if b1 >> 7 == 0 { return Ok(Self(b1)) }
         // (10) 111111 11111111
         if b1 >> 6 == 0b10 {
-            let b2 = u8::decode::<CONFIG>(c)? as u32;
+            let b2 = c.take_byte()? as u32;
             return Ok(Self((b1 & 0x3F) << 8 | b2))
         }
         // (11) 111111 11111111 11111111
-        let [b2, b3] = <&[u8; 2]>::decode::<CONFIG>(c)?;
-        let (b2, b3) = (*b2 as u32, *b3 as u32);
+        let [b2, b3] = c.take_array()?;
+        let (b2, b3) = (b2 as u32, b3 as u32);
         Ok(Self(((b1 & 0x3F) << 16) | (b2 << 8) | b3))
     }
 );
@@ -158,7 +184,8 @@ def!(
     /// |  111   |   4    |     29      | 0..536870912 |
     BEU29(u32),
     BITS: 29,
-    UsizeTryFromErr: std::num::TryFromIntError,
+    MAX_ENCODED_SIZE: 4,
+    UsizeTryFromErr: core::num::TryFromIntError,
     fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
         let num = self.0;
         let b4 = num as u8;
@@ -179,24 +206,32 @@ def!(
         let b1 = (num >> 24) as u8; // next 8 bits
         c.write_all(&[0xE0 | b1, b2, b3, b4])
     },
-    fn decode<const CONFIG: u16>(c: &mut &[u8]) -> Result<Self> {
-        let b1 = u8::decode::<CONFIG>(c)? as u32;
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        match self.0 {
+            0..=0x7F => 1,
+            0x80..=0x3FFF => 2,
+            0x4000..=0x1F_FFFF => 3,
+            _ => 4,
+        }
+    },
+    fn decode_from(c: &mut impl utils::Input) -> Result<Self> {
+        let b1 = c.take_byte()? as u32;
         // (0) 1111111
         if b1 >> 7 == 0b0 { return Ok(Self(b1)) }
         // (10) 111111 11111111
         if b1 >> 6 == 0b10 {
-            let b2 = u8::decode::<CONFIG>(c)? as u32;
+            let b2 = c.take_byte()? as u32;
             return Ok(Self((b1 & 0x3F) << 8 | b2));
         }
         // (110) 11111  11111111 | 11111111
         if b1 >> 5 == 0b110 {
-            let [b2, b3] = <&[u8; 2]>::decode::<CONFIG>(c)?;
-            let (b2, b3) = (*b2 as u32, *b3 as u32);
+            let [b2, b3] = c.take_array()?;
+            let (b2, b3) = (b2 as u32, b3 as u32);
             return Ok(Self((b1 & 0b11111) << 16 | b2 << 8 | b3));
         }
         // (111) 11111 | 11111111 | 11111111 | 11111111
-        let [b2, b3, b4] = <&[u8; 3]>::decode::<CONFIG>(c)?;
-        let (b2, b3, b4) = (*b2 as u32, *b3 as u32, *b4 as u32);
+        let [b2, b3, b4] = c.take_array()?;
+        let (b2, b3, b4) = (b2 as u32, b3 as u32, b4 as u32);
         Ok(Self((b1 & 0b11111) << 24 | b2 << 16 | b3 << 8 | b4))
     }
 );
@@ -214,7 +249,8 @@ def!(
     /// |  11   |   4    |     30      | 0..1073741824 |
     BEU30(u32),
     BITS: 30,
-    UsizeTryFromErr: std::num::TryFromIntError,
+    MAX_ENCODED_SIZE: 4,
+    UsizeTryFromErr: core::num::TryFromIntError,
     fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
         let num = self.0;
         let b4 = num as u8;
@@ -238,8 +274,16 @@ def!(
         c.write_all(&[0xC0 | b1, b2, b3, b4])
     },
 
-    fn decode<const CONFIG: u16>(c: &mut &[u8]) -> Result<Self> {
-        let b1 = u8::decode::<CONFIG>(c)? as u32;
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        match self.0 {
+            0..=0x3F => 1,
+            0x40..=0x3FFF => 2,
+            0x4000..=0x3F_FFFF => 3,
+            _ => 4,
+        }
+    },
+    fn decode_from(c: &mut impl utils::Input) -> Result<Self> {
+        let b1 = c.take_byte()? as u32;
         let len = b1 >> 6;
         // (00) 111111
         if len == 0 { return Ok(Self(b1)) }
@@ -247,18 +291,354 @@ def!(
         let b1 = b1 & 0x3F;
         // (01) 111111 11111111
         if len == 1 {
-            let b2 = u8::decode::<CONFIG>(c)? as u32;
+            let b2 = c.take_byte()? as u32;
             return Ok(Self(b1 << 8 | b2));
         }
         // (10) 111111 11111111 11111111
         if len == 2 {
-            let [b2, b3] = <&[u8; 2]>::decode::<CONFIG>(c)?;
-            let (b2, b3) = (*b2 as u32, *b3 as u32);
+            let [b2, b3] = c.take_array()?;
+            let (b2, b3) = (b2 as u32, b3 as u32);
             return Ok(Self(b1 << 16 | b2 << 8 | b3));
         }
         // (11) 111111 11111111 11111111 11111111
-        let [b2, b3, b4] = <&[u8; 3]>::decode::<CONFIG>(c)?;
-        let (b2, b3, b4) = (*b2 as u32, *b3 as u32, *b4 as u32);
+        let [b2, b3, b4] = c.take_array()?;
+        let (b2, b3, b4) = (b2 as u32, b3 as u32, b4 as u32);
         Ok(Self(b1 << 24 | b2 << 16 | b3 << 8 | b4))
     }
 );
+
+def!(
+    /// [BEU62] is variable-length encoder type for non-negative integer values.
+    ///
+    /// It is the variable-length integer encoding of QUIC, See: [RFC 9000](https://www.rfc-editor.org/rfc/rfc9000.html#name-variable-length-integer-enc)
+    ///
+    /// |  MSB  | Length | Usable Bits | Range                    |
+    /// | :---: | :----: | :---------: | :----------------------- |
+    /// |  00   |   1    |      6      | 0..64                    |
+    /// |  01   |   2    |     14      | 0..16384                 |
+    /// |  10   |   4    |     30      | 0..1073741824            |
+    /// |  11   |   8    |     62      | 0..4611686018427387904   |
+    BEU62(u64),
+    BITS: 62,
+    MAX_ENCODED_SIZE: 8,
+    UsizeTryFromErr: core::num::TryFromIntError,
+    fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
+        let num = self.0;
+        // (00) 111111
+        if num < (1 << 6) { return c.write_all(&[num as u8]) }
+        // (01) 111111 11111111
+        if num < (1 << 14) {
+            return c.write_all(&(0x4000 | num as u16).to_be_bytes())
+        }
+        // (10) 111111 11111111 11111111 11111111
+        if num < (1 << 30) {
+            return c.write_all(&(0x8000_0000 | num as u32).to_be_bytes())
+        }
+        // (11) 111111 11111111 11111111 11111111 11111111 11111111 11111111 11111111
+        if num > Self::MAX {
+            return Err(utils::invalid_input(error::IntegerOverflow));
+        }
+        c.write_all(&(0xC000_0000_0000_0000 | num).to_be_bytes())
+    },
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        Self::len_of(self.0)
+    },
+    pub(crate) fn decode_from(c: &mut impl utils::Input) -> Result<Self> {
+        let b1 = c.take_byte()?;
+        let b1_value = (b1 & 0x3F) as u64;
+        Ok(Self(match b1 >> 6 {
+            // (00) 111111
+            0 => b1_value,
+            // (01) 111111 11111111
+            1 => {
+                let [b2] = c.take_array()?;
+                b1_value << 8 | b2 as u64
+            }
+            // (10) 111111 11111111 11111111 11111111
+            2 => {
+                let rest: [u8; 3] = c.take_array()?;
+                b1_value << 24 | u32::from_be_bytes([0, rest[0], rest[1], rest[2]]) as u64
+            }
+            // (11) 111111 11111111 11111111 11111111 11111111 11111111 11111111 11111111
+            _ => {
+                let rest: [u8; 7] = c.take_array()?;
+                let mut bytes = [0; 8];
+                bytes[1..].copy_from_slice(&rest);
+                b1_value << 56 | u64::from_be_bytes(bytes)
+            }
+        }))
+    }
+);
+
+impl BEU62 {
+    /// Number of bytes required to encode `num`.
+    pub(crate) const fn len_of(num: u64) -> usize {
+        match num {
+            0..=0x3F => 1,
+            0x40..=0x3FFF => 2,
+            0x4000..=0x3FFF_FFFF => 4,
+            _ => 8,
+        }
+    }
+}
+
+/// Number of significant bits in `num`.
+#[inline]
+const fn bits_of(num: u64) -> u32 {
+    u64::BITS - num.leading_zeros()
+}
+
+/// Number of 7-bit groups required to represent `num`.
+#[inline]
+const fn groups_of(num: u64) -> usize {
+    let bits = bits_of(num) as usize;
+    if bits == 0 {
+        1
+    } else {
+        bits.div_ceil(7)
+    }
+}
+
+#[inline]
+fn canonical<T>(value: T, len: usize, canonical_len: usize) -> Result<T> {
+    if len == canonical_len {
+        Ok(value)
+    } else {
+        Err(Error::new(error::ErrorKind::NonCanonical))
+    }
+}
+
+/// Write `num` in big-endian base-128, Using `len` groups.
+#[inline]
+fn encode_base128(num: u64, len: usize, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
+    let mut buf = [0; 10];
+    for (i, byte) in buf[..len].iter_mut().enumerate() {
+        let shift = 7 * (len - 1 - i);
+        *byte = (num >> shift) as u8 & 0x7F | if i + 1 < len { 0x80 } else { 0 };
+    }
+    c.write_all(&buf[..len])
+}
+
+def!(
+    /// [VLQ] (Variable-length quantity) is big-endian base-128 encoding, used by MIDI and Git.
+    ///
+    /// Every byte holds 7 bits of the number, The most significant group first.
+    /// The MSB of each byte is set, If more bytes follow.
+    ///
+    /// | Length | Usable Bits | Range        |
+    /// | :----: | :---------: | :----------- |
+    /// |   1    |      7      | 0..128       |
+    /// |   2    |     14      | 0..16384     |
+    /// |   n    |    7 * n    | 0..2^(7 * n) |
+    /// |   10   |     64      | 0..2^64      |
+    ///
+    /// Decoding fails with [NonCanonical](error::ErrorKind::NonCanonical) error, If the number has leading zero groups.
+    VLQ(u64),
+    BITS: 64,
+    MAX_ENCODED_SIZE: 10,
+    UsizeTryFromErr: core::num::TryFromIntError,
+    fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
+        encode_base128(self.0, Self::len_of(self.0), c)
+    },
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        Self::len_of(self.0)
+    },
+    pub(crate) fn decode_from(c: &mut impl utils::Input) -> Result<Self> {
+        let mut num: u64 = 0;
+        let mut len = 0;
+        loop {
+            let byte = c.take_byte()?;
+            if num >> 57 != 0 {
+                return Err(Error::from(error::IntegerOverflow));
+            }
+            num = num << 7 | (byte & 0x7F) as u64;
+            len += 1;
+            // Leading zero group, Would be followed by unbounded number of zero groups.
+            if num == 0 && byte & 0x80 != 0 {
+                return Err(Error::new(error::ErrorKind::NonCanonical));
+            }
+            if byte & 0x80 == 0 {
+                break canonical(Self(num), len, Self::len_of(num));
+            }
+        }
+    }
+);
+
+impl VLQ {
+    /// Number of bytes required to encode `num`.
+    pub(crate) const fn len_of(num: u64) -> usize {
+        groups_of(num)
+    }
+}
+
+def!(
+    /// [CompactSize] is a variable-length encoding used by Bitcoin.
+    ///
+    /// | First byte | Length | Range                |
+    /// | :--------: | :----: | :------------------- |
+    /// | `0..=0xFC` |   1    | 0..253               |
+    /// |   `0xFD`   |   3    | 253..65536 (`u16` LE)|
+    /// |   `0xFE`   |   5    | 65536..2^32 (`u32` LE)|
+    /// |   `0xFF`   |   9    | 2^32..2^64 (`u64` LE)|
+    ///
+    /// Decoding fails with [NonCanonical](error::ErrorKind::NonCanonical) error, If the number is not encoded in the shortest form.
+    CompactSize(u64),
+    BITS: 64,
+    MAX_ENCODED_SIZE: 9,
+    UsizeTryFromErr: core::num::TryFromIntError,
+    fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
+        let num = self.0;
+        match Self::len_of(num) {
+            1 => c.write_all(&[num as u8]),
+            3 => { c.write_all(&[0xFD])?; c.write_all(&(num as u16).to_le_bytes()) }
+            5 => { c.write_all(&[0xFE])?; c.write_all(&(num as u32).to_le_bytes()) }
+            _ => { c.write_all(&[0xFF])?; c.write_all(&num.to_le_bytes()) }
+        }
+    },
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        Self::len_of(self.0)
+    },
+    pub(crate) fn decode_from(c: &mut impl utils::Input) -> Result<Self> {
+        let (num, len) = match c.take_byte()? {
+            0xFD => (u16::from_le_bytes(c.take_array()?) as u64, 3),
+            0xFE => (u32::from_le_bytes(c.take_array()?) as u64, 5),
+            0xFF => (u64::from_le_bytes(c.take_array()?), 9),
+            byte => (byte as u64, 1),
+        };
+        canonical(Self(num), len, Self::len_of(num))
+    }
+);
+
+impl CompactSize {
+    /// Number of bytes required to encode `num`.
+    pub(crate) const fn len_of(num: u64) -> usize {
+        match num {
+            0..=0xFC => 1,
+            0xFD..=0xFFFF => 3,
+            0x1_0000..=0xFFFF_FFFF => 5,
+            _ => 9,
+        }
+    }
+}
+
+def!(
+    /// [SqliteVarint] is the big-endian variable-length encoding used by SQLite.
+    ///
+    /// The first 8 bytes hold 7 bits each (like [VLQ]), The MSB is set If more bytes follow.
+    /// The 9th byte (If any) holds all 8 bits.
+    ///
+    /// | Length | Usable Bits | Range        |
+    /// | :----: | :---------: | :----------- |
+    /// |   1    |      7      | 0..128       |
+    /// |   n    |    7 * n    | 0..2^(7 * n) |
+    /// |   8    |     56      | 0..2^56      |
+    /// |   9    |     64      | 0..2^64      |
+    ///
+    /// Decoding fails with [NonCanonical](error::ErrorKind::NonCanonical) error, If the number is not encoded in the shortest form.
+    SqliteVarint(u64),
+    BITS: 64,
+    MAX_ENCODED_SIZE: 9,
+    UsizeTryFromErr: core::num::TryFromIntError,
+    fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
+        let num = self.0;
+        match Self::len_of(num) {
+            9 => {
+                let mut buf = [0; 9];
+                for (i, byte) in buf[..8].iter_mut().enumerate() {
+                    *byte = (num >> (57 - 7 * i)) as u8 & 0x7F | 0x80;
+                }
+                buf[8] = num as u8;
+                c.write_all(&buf)
+            }
+            len => encode_base128(num, len, c),
+        }
+    },
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        Self::len_of(self.0)
+    },
+    pub(crate) fn decode_from(c: &mut impl utils::Input) -> Result<Self> {
+        let mut num: u64 = 0;
+        for len in 1..=8 {
+            let byte = c.take_byte()?;
+            num = num << 7 | (byte & 0x7F) as u64;
+            if byte & 0x80 == 0 {
+                return canonical(Self(num), len, Self::len_of(num));
+            }
+        }
+        num = num << 8 | c.take_byte()? as u64;
+        canonical(Self(num), 9, Self::len_of(num))
+    }
+);
+
+impl SqliteVarint {
+    /// Number of bytes required to encode `num`.
+    pub(crate) const fn len_of(num: u64) -> usize {
+        if num >> 56 != 0 {
+            9
+        } else {
+            groups_of(num)
+        }
+    }
+}
+
+def!(
+    /// [PrefixVarint] is little-endian variable-length encoding, The length is prefixed in unary.
+    ///
+    /// Number of trailing zeros in the first byte plus one, Is the length of the number.
+    /// The rest of the bits hold the number in little-endian. If the first byte is `0`, The next 8 bytes hold the number.
+    ///
+    /// | First byte   | Length | Usable Bits | Range        |
+    /// | :----------- | :----: | :---------: | :----------- |
+    /// | `xxxxxxx1`   |   1    |      7      | 0..128       |
+    /// | `xxxxxx10`   |   2    |     14      | 0..16384     |
+    /// | `10000000`   |   8    |     56      | 0..2^56      |
+    /// | `00000000`   |   9    |     64      | 0..2^64      |
+    ///
+    /// Decoding fails with [NonCanonical](error::ErrorKind::NonCanonical) error, If the number is not encoded in the shortest form.
+    PrefixVarint(u64),
+    BITS: 64,
+    MAX_ENCODED_SIZE: 9,
+    UsizeTryFromErr: core::num::TryFromIntError,
+    fn encode<const CONFIG: u16>(&self, c: &mut (impl Write + ?Sized)) -> io::Result<()> {
+        let num = self.0;
+        match Self::len_of(num) {
+            9 => {
+                c.write_all(&[0])?;
+                c.write_all(&num.to_le_bytes())
+            }
+            len => {
+                let bytes = ((num << 1 | 1) << (len - 1)).to_le_bytes();
+                c.write_all(&bytes[..len])
+            }
+        }
+    },
+    fn encoded_len<const CONFIG: u16>(&self) -> usize {
+        Self::len_of(self.0)
+    },
+    pub(crate) fn decode_from(c: &mut impl utils::Input) -> Result<Self> {
+        let b1 = c.take_byte()?;
+        if b1 == 0 {
+            let num = u64::from_le_bytes(c.take_array()?);
+            return canonical(Self(num), 9, Self::len_of(num));
+        }
+        let len = b1.trailing_zeros() as usize + 1;
+        let mut bytes = [0; 8];
+        bytes[0] = b1;
+        for byte in &mut bytes[1..len] {
+            *byte = c.take_byte()?;
+        }
+        let num = u64::from_le_bytes(bytes) >> len;
+        canonical(Self(num), len, Self::len_of(num))
+    }
+);
+
+impl PrefixVarint {
+    /// Number of bytes required to encode `num`.
+    pub(crate) const fn len_of(num: u64) -> usize {
+        if num >> 56 != 0 {
+            9
+        } else {
+            groups_of(num)
+        }
+    }
+}
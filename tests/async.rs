@@ -0,0 +1,149 @@
+#![cfg(feature = "async")]
+
+use databuf::{
+    async_io::*,
+    config::num::LEB128,
+    error::{ErrorKind, Limit},
+    *,
+};
+use std::{
+    future::Future,
+    pin::{pin, Pin},
+    task::{Context, Poll, Waker},
+};
+
+#[derive(Encode, Decode, PartialEq, Debug, Clone)]
+struct Msg {
+    id: u32,
+    tags: Vec<String>,
+}
+
+fn block_on<F: Future>(fut: F) -> F::Output {
+    let mut fut = pin!(fut);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+            return out;
+        }
+    }
+}
+
+/// Returns at most 3 bytes per read, And `Pending` on every other poll.
+struct Trickle<'a> {
+    data: &'a [u8],
+    pending: bool,
+}
+
+impl AsyncRead for Trickle<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        self.pending = !self.pending;
+        if self.pending {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        let len = buf.len().min(3).min(self.data.len());
+        buf[..len].copy_from_slice(&self.data[..len]);
+        self.data = &self.data[len..];
+        Poll::Ready(Ok(len))
+    }
+}
+
+#[test]
+fn test_async_encode_decode() {
+    let msgs: Vec<Msg> = (0..50)
+        .map(|id| Msg {
+            id,
+            tags: (0..id).map(|i| "tag".repeat(i as usize)).collect(),
+        })
+        .collect();
+
+    let socket = block_on(async {
+        let mut socket = Vec::new();
+        for msg in &msgs {
+            msg.encode_async::<LEB128>(&mut socket).await.unwrap();
+        }
+        socket
+    });
+    let expected: Vec<u8> = msgs.iter().flat_map(|m| m.to_bytes::<LEB128>()).collect();
+    assert_eq!(socket, expected);
+
+    block_on(async {
+        let mut decoder = AsyncDecoder::new(Trickle {
+            data: &socket,
+            pending: false,
+        });
+        for msg in &msgs {
+            assert_eq!(&decoder.decode::<Msg, LEB128>().await.unwrap(), msg);
+        }
+        assert!(decoder.buffer().is_empty());
+
+        let err = decoder.decode::<Msg, LEB128>().await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InsufficientBytes { .. }));
+    });
+}
+
+#[test]
+fn test_async_decode_error() {
+    let bytes = [1, 1, 1, 0xFF];
+    block_on(async {
+        let mut decoder = AsyncDecoder::new(&bytes[..]);
+        let err = decoder.decode::<Msg, LEB128>().await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidUtf8 { .. }));
+    });
+}
+
+#[test]
+fn test_async_limits() {
+    let small = Msg {
+        id: 1,
+        tags: vec!["a".into()],
+    };
+    let large = Msg {
+        id: 2,
+        tags: vec!["b".repeat(100)],
+    };
+    let mut socket = small.to_bytes::<LEB128>();
+    socket.extend(large.to_bytes::<LEB128>());
+    let limits = Limits {
+        max_frame: 16,
+        max_len: 2,
+        ..Limits::default()
+    };
+    block_on(async {
+        let mut decoder = AsyncDecoder::with_limits(
+            Trickle {
+                data: &socket,
+                pending: false,
+            },
+            limits,
+        );
+        assert_eq!(decoder.decode::<Msg, LEB128>().await.unwrap(), small);
+        let err = decoder.decode::<Msg, LEB128>().await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::LimitExceeded(Limit::Frame)));
+        assert!(decoder.buffer().len() <= 16);
+
+        // Other limits are applied to every value.
+        let bytes = [1, 3, 0, 0, 0];
+        let mut decoder = AsyncDecoder::with_limits(&bytes[..], limits);
+        let err = decoder.decode::<Msg, LEB128>().await.unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::LimitExceeded(Limit::Len)));
+    });
+}
+
+#[test]
+fn test_send() {
+    fn assert_send<T: Send>(_: T) {}
+    let msg = Msg {
+        id: 1,
+        tags: vec![],
+    };
+    let mut socket = Vec::new();
+    assert_send(msg.encode_async::<LEB128>(&mut socket));
+
+    let mut decoder = AsyncDecoder::new(&[][..]);
+    assert_send(decoder.decode::<Msg, LEB128>());
+}
@@ -0,0 +1,109 @@
+use databuf::{
+    config::{int_codec, len, num, Config},
+    *,
+};
+
+#[derive(Encode, Decode, PartialEq, Debug)]
+struct Frame {
+    id: u64,
+    offset: i32,
+    payload: Vec<u16>,
+    name: String,
+}
+
+#[test]
+fn test_runtime_config() {
+    let frame = Frame {
+        id: 0xDEAD_BEEF,
+        offset: -42,
+        payload: (0..300).collect(),
+        name: "frame".repeat(40),
+    };
+    macro_rules! check {
+        [$($num: ident)*; $lens: tt; $ints: tt] => {$(
+            check!(@ $num; $lens; $ints);
+        )*};
+        [@ $num: ident; ($($len: ident)*); $ints: tt] => {$(
+            check!(@ $num $len; $ints);
+        )*};
+        [@ $num: ident $len: ident; ($($int: ident)*)] => {$({
+            const CONFIG: u16 = num::$num | len::$len | int_codec::$int;
+            let config = Config::new(CONFIG).unwrap();
+            let bytes = frame.to_bytes_with(config);
+            assert_eq!(bytes, frame.to_bytes::<CONFIG>());
+
+            let mut buf = vec![];
+            frame.encode_with(config, &mut buf).unwrap();
+            assert_eq!(buf, bytes);
+
+            assert_eq!(Frame::from_bytes_with(config, &bytes).unwrap(), frame);
+            assert_eq!(Frame::decode_with(config, &mut &bytes[..]).unwrap(), frame);
+        })*};
+    }
+    check!(
+        LE BE NE LEB128 BEU62 VLQ COMPACT_SIZE SQLITE_VARINT PREFIX_VARINT;
+        (BEU30 BEU29 BEU22 BEU15 BEU62 VLQ COMPACT_SIZE SQLITE_VARINT PREFIX_VARINT U16 U32 U64 LEB128);
+        (ZIG_ZAG)
+    );
+}
+
+#[test]
+fn test_invalid_config() {
+    assert_eq!(Config::default(), Config::new(config::DEFAULT).unwrap());
+    assert_eq!(
+        Config::try_from(num::BE | len::BEU22).unwrap().bits(),
+        num::BE | len::BEU22
+    );
+
+    for bits in [
+        0b1111,
+        num::PREFIX_VARINT + 1,
+        len::LEB128 + (1 << 4),
+        0b1111_0000,
+        int_codec::SIGN_BIT,
+        config::char_codec::UTF8,
+        config::float::BE,
+        1 << 14,
+    ] {
+        let err = Config::new(bits).unwrap_err();
+        assert_eq!(err.bits, bits);
+    }
+}
+
+#[test]
+fn test_len_options() {
+    use databuf::error::ErrorKind;
+
+    fn check<const CONFIG: u16>(data: &[u16], expect: &[u8]) {
+        let bytes = data.to_bytes::<CONFIG>();
+        assert_eq!(bytes[..expect.len()], *expect);
+        assert_eq!(data.encoded_len::<CONFIG>(), bytes.len());
+        assert_eq!(Vec::<u16>::from_bytes::<CONFIG>(&bytes).unwrap(), data);
+        assert_eq!(
+            Vec::<u16>::decode_from_reader::<CONFIG>(&mut &bytes[..]).unwrap(),
+            data
+        );
+    }
+    let data = vec![1; 200];
+    check::<{ num::LE | len::U8 }>(&data, &[200, 1, 0]);
+    check::<{ num::BE | len::U16 }>(&data, &[0, 200, 0, 1]);
+    check::<{ num::LE | len::U32 }>(&data, &[200, 0, 0, 0, 1, 0]);
+    check::<{ num::BE | len::U64 }>(&data, &[0, 0, 0, 0, 0, 0, 0, 200, 0, 1]);
+    check::<{ num::LEB128 | len::U16 }>(&data, &[200, 0, 1]);
+    check::<{ num::LE | len::LEB128 }>(&data, &[0xC8, 1, 1, 0]);
+
+    let err = vec![0_u8; 256]
+        .encode::<{ len::U8 }>(&mut Vec::new())
+        .unwrap_err();
+    assert!(err
+        .get_ref()
+        .unwrap()
+        .is::<databuf::error::IntegerOverflow>());
+    assert_eq!("a".repeat(256).encoded_len::<{ len::U8 }>(), 1 + 256);
+
+    let err = String::from_bytes::<{ len::U16 }>(&[5, 0, b'a']).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::InsufficientBytes { missing: Some(4) }
+    ));
+}
@@ -0,0 +1,66 @@
+//! Derives with `#[databuf(crate = "...")]`, Where `databuf` is only reachable through a re-export.
+#![cfg(test)]
+
+mod sdk {
+    pub use wire;
+}
+
+use sdk::wire::{config::num::LEB128, error, Decode, DecodeFromReader, Encode, MaxEncodedSize};
+
+#[derive(sdk::wire::Encode, sdk::wire::Decode, PartialEq, Debug)]
+#[databuf(crate = "sdk::wire", config = num::LE | len::U8)]
+struct Header {
+    #[databuf(bits = 4)]
+    version: u8,
+    #[databuf(bits = 4)]
+    flags: u8,
+    #[databuf(num = BE)]
+    id: u16,
+    name: String,
+}
+
+#[derive(sdk::wire::Encode, sdk::wire::Decode, PartialEq, Debug)]
+#[databuf(crate = "sdk::wire")]
+enum Message {
+    Ping,
+    Data(Header),
+}
+
+#[derive(sdk::wire::Encode, sdk::wire::Decode, PartialEq, Debug)]
+#[databuf(crate = "sdk::wire", discriminant = LEB128)]
+enum Level {
+    Low,
+    High,
+}
+
+#[derive(sdk::wire::Encode, sdk::wire::Decode, PartialEq, Debug)]
+#[databuf(crate = "sdk::wire", transparent)]
+struct Id<T>(#[databuf(num = BE)] T);
+
+#[test]
+fn test_crate_path() {
+    let message = Message::Data(Header {
+        version: 1,
+        flags: 2,
+        id: 3,
+        name: "a".into(),
+    });
+    let bytes = message.to_bytes::<LEB128>();
+    assert_eq!(bytes, [1, 0x12, 0, 3, 1, b'a']);
+    assert_eq!(Message::from_bytes::<LEB128>(&bytes).unwrap(), message);
+    assert_eq!(
+        Message::decode_from_reader::<LEB128>(&mut &bytes[..]).unwrap(),
+        message
+    );
+    assert_eq!(Level::from_bytes::<LEB128>(&[1]).unwrap(), Level::High);
+    assert!(matches!(
+        Level::from_bytes::<LEB128>(&[2]).unwrap_err().kind(),
+        error::ErrorKind::UnknownDiscriminant { .. }
+    ));
+
+    let bytes = Id(1_u16).to_bytes::<LEB128>();
+    assert_eq!(bytes, [0, 1]);
+    assert_eq!(Id::<u16>::from_bytes::<LEB128>(&bytes).unwrap(), Id(1));
+    // Generic fields with overridden options are unbounded.
+    assert_eq!(<Id<u16> as MaxEncodedSize<LEB128>>::MAX_ENCODED_SIZE, None);
+}
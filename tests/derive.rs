@@ -10,6 +10,7 @@ struct Object<'a, T, Byte, const N: usize> {
 }
 
 #[repr(u8)]
+#[allow(clippy::enum_variant_names)]
 #[derive(Encode, Decode, PartialEq, Debug)]
 enum Data<'a, T> {
     Unit,
@@ -31,6 +32,1037 @@ fn test_derive() {
         let buf: &mut dyn Write = &mut bytes;
         obj.encode::<LEB128>(buf).unwrap();
     }
+    assert_eq!(obj.encoded_len::<LEB128>(), bytes.len());
     let new_obj = Object::from_bytes::<LEB128>(&bytes).unwrap();
     assert_eq!(obj, new_obj);
 }
+
+#[derive(Encode, Decode, PartialEq, Debug)]
+struct Company {
+    name: String,
+    cars: Vec<Car>,
+}
+
+#[derive(Encode, Decode, PartialEq, Debug)]
+struct Car {
+    name: Box<str>,
+    year: u16,
+    kind: Option<Kind>,
+}
+
+#[derive(Encode, Decode, PartialEq, Debug, Clone)]
+enum Kind {
+    Electric { range: u32 },
+    Fuel(f32),
+}
+
+#[test]
+fn test_decode_from_reader() {
+    let company = Company {
+        name: "Tesla".into(),
+        cars: vec![
+            Car {
+                name: "Model S".into(),
+                year: 2018,
+                kind: Some(Kind::Electric { range: 405 }),
+            },
+            Car {
+                name: "Model X".into(),
+                year: 2019,
+                kind: Some(Kind::Fuel(0.5)),
+            },
+            Car {
+                name: "Roadster".into(),
+                year: 2008,
+                kind: None,
+            },
+        ],
+    };
+    let mut bytes = company.to_bytes::<LEB128>();
+    bytes.extend_from_slice(&[1, 2, 3]);
+
+    let mut reader = std::io::Cursor::new(&bytes);
+    let new_company = Company::decode_from_reader::<LEB128>(&mut reader).unwrap();
+    assert_eq!(company, new_company);
+    assert_eq!(reader.position() as usize, bytes.len() - 3);
+
+    let mut reader: &[u8] = &bytes[..bytes.len() - 5];
+    assert!(Company::decode_from_reader::<LEB128>(&mut reader).is_err());
+}
+
+#[test]
+fn test_encoded_len() {
+    use databuf::config::{len, num};
+
+    fn check<const CONFIG: u16>(value: &impl Encode) {
+        assert_eq!(
+            value.encoded_len::<CONFIG>(),
+            value.to_bytes::<CONFIG>().len()
+        );
+    }
+    let company = Company {
+        name: "x".repeat(300),
+        cars: (0..100)
+            .map(|i| Car {
+                name: "y".repeat(i * 3).into(),
+                year: 1900 + i as u16 * 8,
+                kind: [
+                    None,
+                    Some(Kind::Electric {
+                        range: i as u32 * 1000,
+                    }),
+                    Some(Kind::Fuel(1.5)),
+                ][i % 3]
+                    .clone(),
+            })
+            .collect(),
+    };
+    check::<{ num::LE | len::BEU30 }>(&company);
+    check::<{ num::BE | len::BEU29 }>(&company);
+    check::<{ num::NE | len::BEU22 }>(&company);
+    check::<{ num::LEB128 | len::BEU15 }>(&company);
+    check::<LEB128>(&(i64::MIN, -1_i32, u128::MAX, 'a', [true; 3], "Hello"));
+}
+
+#[derive(Encode)]
+struct Reading {
+    sensor: u8,
+    value: f32,
+    kind: Kind,
+}
+
+#[repr(u8)]
+#[derive(Encode)]
+enum Level {
+    Low,
+    High = 10,
+}
+
+#[test]
+fn test_max_encoded_size() {
+    use databuf::config::num::BE;
+
+    assert_eq!(<Kind as MaxEncodedSize<BE>>::MAX_ENCODED_SIZE, Some(2 + 4));
+    assert_eq!(
+        <Kind as MaxEncodedSize<LEB128>>::MAX_ENCODED_SIZE,
+        Some(2 + 5)
+    );
+    assert_eq!(<Level as MaxEncodedSize<BE>>::MAX_ENCODED_SIZE, Some(1));
+    assert_eq!(Level::Low.to_array::<BE, 1>(), ([0], 1));
+    assert_eq!(Level::High.to_array::<BE, 1>(), ([10], 1));
+    assert_eq!(
+        <Reading as MaxEncodedSize<BE>>::MAX_ENCODED_SIZE,
+        Some(1 + 4 + 6)
+    );
+    assert_eq!(<Company as MaxEncodedSize<BE>>::MAX_ENCODED_SIZE, None);
+    assert_eq!(<Option<Car> as MaxEncodedSize<BE>>::MAX_ENCODED_SIZE, None);
+
+    let reading = Reading {
+        sensor: 7,
+        value: 1.0,
+        kind: Kind::Electric { range: 300 },
+    };
+    let (buf, len) = reading.to_array::<BE, 11>();
+    assert_eq!(&buf[..len], reading.to_bytes::<BE>());
+    assert_eq!(len, 1 + 4 + 1 + 4);
+}
+
+#[test]
+fn test_decode_error() {
+    use databuf::error::ErrorKind;
+
+    let err = Kind::from_bytes::<LEB128>(&[7]).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::UnknownDiscriminant { ident, discriminant: 7 } if ident.ends_with("::Kind")
+    ));
+    assert_eq!(err.offset(), Some(1));
+
+    let err = Car::from_bytes::<LEB128>(&[3, b'a', 0xC3, 0x28]).unwrap_err();
+    match err.kind() {
+        ErrorKind::InvalidUtf8 { valid_up_to, bytes } => {
+            assert_eq!(*valid_up_to, 1);
+            assert_eq!(bytes, &[0xC3]);
+        }
+        kind => panic!("unexpected error: {kind}"),
+    }
+    assert_eq!(err.offset(), Some(4));
+
+    let err = Car::from_bytes::<LEB128>(&[5, b'a']).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::InsufficientBytes { missing: Some(4) }
+    ));
+    assert_eq!(err.offset(), Some(1));
+
+    let mut reader: &[u8] = &[1, b'a', 1];
+    let err = Car::decode_from_reader::<LEB128>(&mut reader).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::InsufficientBytes { missing: None }
+    ));
+    assert_eq!(err.offset(), None);
+}
+
+#[test]
+fn test_error_path() {
+    use databuf::error::PathSegment;
+
+    let company = Company {
+        name: "Tesla".into(),
+        cars: vec![
+            Car {
+                name: "Model S".into(),
+                year: 2018,
+                kind: None,
+            },
+            Car {
+                name: "Model X".into(),
+                year: 2019,
+                kind: None,
+            },
+        ],
+    };
+    let bytes = company.to_bytes::<LEB128>();
+    let err = Company::from_bytes::<LEB128>(&bytes[..21]).unwrap_err();
+    let path = err.path().unwrap();
+    assert_eq!(path.to_string(), "Company.cars[1].name");
+    assert_eq!(path.root(), Some("Company"));
+    assert_eq!(
+        path.segments().copied().collect::<Vec<_>>(),
+        [
+            PathSegment::Field("cars"),
+            PathSegment::Index(1),
+            PathSegment::Field("name")
+        ]
+    );
+    assert_eq!(
+        err.to_string(),
+        "insufficient bytes: 5 more required in `Company.cars[1].name` at byte offset 19"
+    );
+
+    let err = Data::<u8>::from_bytes::<LEB128>(&[2, 3, 1]).unwrap_err();
+    assert_eq!(err.path().unwrap().to_string(), "Data::Ref.data");
+
+    let err = Data::<u8>::from_bytes::<LEB128>(&[3, 1]).unwrap_err();
+    assert_eq!(err.path().unwrap().to_string(), "Data::Data.1");
+
+    let err = Kind::from_bytes::<LEB128>(&[]).unwrap_err();
+    assert_eq!(err.path().unwrap().to_string(), "Kind");
+
+    let err = Vec::<bool>::from_bytes::<LEB128>(&[2, 1, 2]).unwrap_err();
+    assert_eq!(err.path().unwrap().to_string(), "[1]");
+    assert!(u8::from_bytes::<LEB128>(&[]).unwrap_err().path().is_none());
+}
+
+#[test]
+fn test_from_bytes_exact() {
+    use databuf::config::{len, num};
+    use databuf::error::ErrorKind;
+
+    fn check<const CONFIG: u16>() {
+        let car = Car {
+            name: "Model 3".into(),
+            year: 2017,
+            kind: Some(Kind::Electric { range: 358 }),
+        };
+        let mut bytes = car.to_bytes::<CONFIG>();
+        let len = bytes.len();
+        assert_eq!(Car::from_bytes_exact::<CONFIG>(&bytes).unwrap(), car);
+
+        bytes.extend_from_slice(&[1, 2]);
+        let err = Car::from_bytes_exact::<CONFIG>(&bytes).unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::TrailingBytes { remaining: 2 }
+        ));
+        assert_eq!(err.offset(), Some(len));
+
+        let (new_car, rest) = Car::from_bytes_with_rest::<CONFIG>(&bytes).unwrap();
+        assert_eq!(new_car, car);
+        assert_eq!(rest, [1, 2]);
+    }
+    check::<{ num::LE | len::BEU30 }>();
+    check::<{ num::BE | len::BEU29 }>();
+    check::<{ num::NE | len::BEU22 }>();
+    check::<{ num::LEB128 | len::BEU15 }>();
+}
+
+#[derive(Encode, Decode, PartialEq, Debug)]
+struct Packet<T> {
+    #[databuf(num = BE)]
+    magic: u32,
+    #[databuf(num = LEB128)]
+    counter: u64,
+    #[databuf(len = BEU15)]
+    payload: Vec<u8>,
+    #[databuf(num = LEB128, int_codec = TWOS_COMPLEMENT)]
+    offset: i32,
+    value: T,
+}
+
+#[derive(Encode, Decode, PartialEq, Debug)]
+#[databuf(config = num::BE | len::BEU15)]
+struct Pinned {
+    id: u16,
+    name: String,
+    #[databuf(num = LE)]
+    tag: u16,
+}
+
+#[derive(Encode, Decode, PartialEq, Debug)]
+enum Message {
+    Ping(#[databuf(num = BE)] u16),
+    Data {
+        #[databuf(len = BEU62)]
+        bytes: Vec<u8>,
+    },
+}
+
+#[test]
+fn test_field_config() {
+    use databuf::config::{len, num};
+
+    let packet = Packet {
+        magic: 0xCAFE_BABE,
+        counter: 300,
+        payload: vec![7; 200],
+        offset: -2,
+        value: 1_u16,
+    };
+    let bytes = packet.to_bytes::<{ num::LE | len::BEU30 }>();
+    let expected = [
+        &[0xCA, 0xFE, 0xBA, 0xBE][..],
+        &[0xAC, 0x02],
+        &[0x80, 200],
+        &[7; 200],
+        &[0x7E],
+        &[1, 0],
+    ]
+    .concat();
+    assert_eq!(bytes, expected);
+    assert_eq!(packet.encoded_len::<{ num::LE }>(), bytes.len());
+    assert_eq!(Packet::from_bytes::<{ num::LE }>(&bytes).unwrap(), packet);
+    assert_eq!(
+        Packet::decode_from_reader::<{ num::LE }>(&mut &bytes[..]).unwrap(),
+        packet
+    );
+
+    // Only `value` follows the config of the caller.
+    let bytes = packet.to_bytes::<{ num::BE | len::BEU22 }>();
+    assert_eq!(bytes[bytes.len() - 2..], [0, 1]);
+    assert_eq!(bytes[..expected.len() - 2], expected[..expected.len() - 2]);
+
+    let pinned = Pinned {
+        id: 1,
+        name: "Hi".into(),
+        tag: 2,
+    };
+    let bytes = [0, 1, 2, b'H', b'i', 2, 0];
+    assert_eq!(pinned.to_bytes::<{ num::LE }>(), bytes);
+    assert_eq!(pinned.to_bytes::<{ num::LEB128 | len::BEU62 }>(), bytes);
+    assert_eq!(Pinned::from_bytes::<{ num::NE }>(&bytes).unwrap(), pinned);
+    assert_eq!(
+        <Pinned as MaxEncodedSize<{ num::LEB128 }>>::MAX_ENCODED_SIZE,
+        None
+    );
+
+    let ping = Message::Ping(1);
+    assert_eq!(ping.to_bytes::<{ num::LE }>(), [0, 0, 1]);
+    assert_eq!(
+        Message::from_bytes::<{ num::LE }>(&[0, 0, 1]).unwrap(),
+        ping
+    );
+    let data = Message::Data { bytes: vec![1; 64] };
+    let bytes = data.to_bytes::<{ num::LE }>();
+    assert_eq!(bytes[..3], [1, 0x40, 64]);
+    assert_eq!(Message::from_bytes::<{ num::LE }>(&bytes).unwrap(), data);
+
+    assert_eq!(
+        <Packet<u16> as MaxEncodedSize<{ num::LE }>>::MAX_ENCODED_SIZE,
+        None
+    );
+    #[derive(Encode, Decode)]
+    struct Header {
+        #[databuf(num = LEB128)]
+        len: u32,
+        #[databuf(num = BE)]
+        kind: u16,
+    }
+    assert_eq!(
+        <Header as MaxEncodedSize<{ num::LE }>>::MAX_ENCODED_SIZE,
+        Some(5 + 2)
+    );
+
+    // Fields with the same options share a helper, If their types read the same options of the caller.
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    struct Tagged<T> {
+        #[databuf(num = BE)]
+        tag: T,
+        #[databuf(num = BE)]
+        id: u16,
+    }
+    let tagged = Tagged { tag: 1_u32, id: 2 };
+    let bytes = tagged.to_bytes::<{ num::LEB128 }>();
+    assert_eq!(bytes, [0, 0, 0, 1, 0, 2]);
+    assert_eq!(
+        Tagged::<u32>::decode_from_reader::<{ num::LEB128 }>(&mut &bytes[..]).unwrap(),
+        tagged
+    );
+    assert_eq!(
+        <Tagged<u32> as MaxEncodedSize<{ num::LE }>>::MAX_ENCODED_SIZE,
+        None
+    );
+}
+
+#[test]
+fn test_float_field_config() {
+    use databuf::config::{float, num};
+
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    struct Telemetry {
+        #[databuf(float = F16)]
+        temp: f32,
+        #[databuf(num = LE)]
+        pressure: f32,
+    }
+    let telemetry = Telemetry {
+        temp: 1.5,
+        pressure: 2.0,
+    };
+    let bytes = telemetry.to_bytes::<{ num::LE }>();
+    assert_eq!(bytes, [0x00, 0x3E, 0, 0, 0, 0x40]);
+    assert_eq!(
+        Telemetry::from_bytes::<{ num::LE }>(&bytes).unwrap(),
+        telemetry
+    );
+
+    // `float` options of the caller apply to the fields with overridden options.
+    let bytes = telemetry.to_bytes::<{ num::BE | float::BE }>();
+    assert_eq!(bytes, [0x3E, 0, 0x40, 0, 0, 0]);
+    assert_eq!(
+        Telemetry::from_bytes::<{ num::BE | float::BE }>(&bytes).unwrap(),
+        telemetry
+    );
+    let nan = Telemetry {
+        temp: 1.5,
+        pressure: -f32::NAN,
+    };
+    let bytes = nan.to_bytes::<{ num::BE | float::CANONICAL_NAN }>();
+    assert_eq!(bytes[2..], f32::NAN.to_le_bytes());
+}
+
+#[test]
+fn test_char_codec_field_config() {
+    use databuf::config::num;
+
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    struct Label {
+        #[databuf(char_codec = UTF8)]
+        icon: char,
+        text: Vec<char>,
+    }
+    let label = Label {
+        icon: '★',
+        text: vec!['é'],
+    };
+    let bytes = label.to_bytes::<{ num::LE }>();
+    assert_eq!(bytes, [0xE2, 0x98, 0x85, 1, 0xE9, 0, 0, 0]);
+    assert_eq!(Label::from_bytes::<{ num::LE }>(&bytes).unwrap(), label);
+    let bytes = label.to_bytes::<{ num::LE | databuf::config::char_codec::UTF8 }>();
+    assert_eq!(bytes, [0xE2, 0x98, 0x85, 1, 0xC3, 0xA9]);
+}
+
+#[derive(Encode, Decode, PartialEq, Debug, Clone, Copy)]
+enum Flag {
+    Off,
+    On,
+    Auto,
+}
+
+#[derive(Encode, Decode, PartialEq, Debug)]
+struct Bits {
+    #[databuf(bits = 3)]
+    version: u8,
+    #[databuf(bits = 1)]
+    urgent: bool,
+    #[databuf(bits = 2)]
+    flag: Flag,
+    #[databuf(bits = 6)]
+    offset: i8,
+    #[databuf(bits = 4)]
+    ttl: u16,
+    checksum: u16,
+}
+
+#[derive(Encode, Decode, PartialEq, Debug)]
+#[databuf(bit_order = LsbFirst)]
+enum Frame {
+    Data(#[databuf(bits = 4)] u8, #[databuf(bits = 4)] u8),
+    Close,
+}
+
+#[test]
+fn test_bit_fields() {
+    use config::num;
+
+    let value = Bits {
+        version: 0b101,
+        urgent: true,
+        flag: Flag::Auto,
+        offset: -3,
+        ttl: 9,
+        checksum: 0xABCD,
+    };
+    // version: 101, urgent: 1, flag: 10, offset: 111101, ttl: 1001
+    let bytes = [0b1011_1011, 0b1101_1001, 0xAB, 0xCD];
+    assert_eq!(value.to_bytes::<{ num::BE }>(), bytes);
+    assert_eq!(value.encoded_len::<{ num::BE }>(), 4);
+    assert_eq!(Bits::from_bytes::<{ num::BE }>(&bytes).unwrap(), value);
+    assert_eq!(
+        Bits::decode_from_reader::<{ num::BE }>(&mut &bytes[..]).unwrap(),
+        value
+    );
+    assert_eq!(
+        <Bits as MaxEncodedSize<{ num::LE }>>::MAX_ENCODED_SIZE,
+        Some(4)
+    );
+
+    let frame = Frame::Data(1, 2);
+    assert_eq!(frame.to_bytes::<{ num::LE }>(), [0, 0x21]);
+    assert_eq!(Frame::from_bytes::<{ num::LE }>(&[0, 0x21]).unwrap(), frame);
+    assert_eq!(frame.encoded_len::<{ num::LE }>(), 2);
+    // `BEU15` discriminant takes up to 2 bytes.
+    assert_eq!(
+        <Frame as MaxEncodedSize<{ num::LE }>>::MAX_ENCODED_SIZE,
+        Some(2 + 1)
+    );
+
+    // Value doesn't fit in its bits.
+    let overflow = Bits {
+        version: 8,
+        ..value
+    };
+    assert!(overflow.encode::<{ num::BE }>(&mut Vec::new()).is_err());
+
+    // Unknown discriminant of a bit field.
+    let err = Bits::from_bytes::<{ num::BE }>(&[0b0000_1100, 0, 0, 0]).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        error::ErrorKind::UnknownDiscriminant {
+            discriminant: 3,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_unaligned_bit_fields() {
+    use databuf::bits::{BitOrder, BitReader, BitWriter};
+
+    // Derived types are checked at compile time, Manual runs fail when they end.
+    let mut bits = BitWriter::new(BitOrder::MsbFirst);
+    bits.write(&mut Vec::new(), &1_u8, 3).unwrap();
+    assert!(bits.finish().is_err());
+
+    let mut bits = BitReader::new(BitOrder::MsbFirst);
+    let _: u8 = bits.read(&mut &[0, 0][..], 3).unwrap();
+    let err = bits.finish().unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        error::ErrorKind::UnalignedBits { remaining: 5 }
+    ));
+}
+
+#[test]
+fn test_discriminant_encoding() {
+    use databuf::{config::num, error::ErrorKind};
+
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    #[databuf(discriminant = U8)]
+    enum Color {
+        Red,
+        Green,
+        Blue = 200,
+    }
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    #[databuf(discriminant = LEB128)]
+    #[repr(u16)]
+    enum Shape {
+        Dot,
+        Line(u8),
+        Rect { w: u8, h: u8 } = 300,
+    }
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    #[databuf(discriminant = BEU15)]
+    #[repr(u32)]
+    enum Level {
+        Low,
+        High = 1000,
+    }
+    // Unit-only enums without `#[repr(...)]` are `i32`, Whatever the pointer width of the host is.
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    enum Mode {
+        Off = -1,
+        On = 1,
+    }
+
+    fn check<const CONFIG: u16, T>(value: T, expect: &[u8])
+    where
+        T: Encode + for<'de> Decode<'de> + DecodeFromReader + PartialEq + std::fmt::Debug,
+    {
+        let bytes = value.to_bytes::<CONFIG>();
+        assert_eq!(bytes, expect);
+        assert_eq!(value.encoded_len::<CONFIG>(), bytes.len());
+        assert_eq!(T::from_bytes::<CONFIG>(&bytes).unwrap(), value);
+        assert_eq!(
+            T::decode_from_reader::<CONFIG>(&mut &bytes[..]).unwrap(),
+            value
+        );
+    }
+    check::<{ num::LE }, _>(Color::Green, &[1]);
+    check::<{ num::BE }, _>(Color::Blue, &[200]);
+    check::<{ num::LE }, _>(Shape::Dot, &[0]);
+    check::<{ num::BE }, _>(Shape::Line(7), &[1, 7]);
+    check::<{ num::LE }, _>(Shape::Rect { w: 1, h: 2 }, &[0xAC, 2, 1, 2]);
+    check::<{ num::LE }, _>(Level::High, &[0x83, 0xE8]);
+    check::<{ num::LE }, _>(Mode::Off, &[0xFF; 4]);
+    check::<{ num::BE }, _>(Mode::On, &[0, 0, 0, 1]);
+
+    assert_eq!(
+        <Color as MaxEncodedSize<{ num::LE }>>::MAX_ENCODED_SIZE,
+        Some(1)
+    );
+    assert_eq!(
+        <Level as MaxEncodedSize<{ num::LE }>>::MAX_ENCODED_SIZE,
+        Some(2)
+    );
+    assert_eq!(
+        <Mode as MaxEncodedSize<{ num::LE }>>::MAX_ENCODED_SIZE,
+        Some(4)
+    );
+
+    let err = Color::from_bytes::<{ num::LE }>(&[2]).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::UnknownDiscriminant {
+            discriminant: 2,
+            ..
+        }
+    ));
+    let err = Shape::from_bytes::<{ num::LE }>(&[0x80, 1]).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::UnknownDiscriminant {
+            discriminant: 128,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_skip_fields() {
+    use databuf::config::num;
+    use std::{cell::Cell, time::Instant};
+
+    fn version() -> u8 {
+        7
+    }
+    #[derive(Encode, Decode, Debug)]
+    struct Session {
+        id: u16,
+        #[databuf(skip)]
+        started: Option<Instant>,
+        #[databuf(default = "version")]
+        version: u8,
+        #[databuf(skip)]
+        cache: Cell<u32>,
+        name: String,
+    }
+    let session = Session {
+        id: 1,
+        started: Some(Instant::now()),
+        version: 2,
+        cache: Cell::new(9),
+        name: "a".into(),
+    };
+    let bytes = session.to_bytes::<{ num::LE }>();
+    assert_eq!(bytes, [1, 0, 1, b'a']);
+    assert_eq!(session.encoded_len::<{ num::LE }>(), bytes.len());
+    let session = Session::from_bytes::<{ num::LE }>(&bytes).unwrap();
+    assert_eq!((session.id, session.started, session.version), (1, None, 7));
+    assert_eq!(session.cache.get(), 0);
+    let session = Session::decode_from_reader::<{ num::LE }>(&mut &bytes[..]).unwrap();
+    assert_eq!(session.version, 7);
+
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    struct Pair(u8, #[databuf(skip)] Vec<u8>, u8);
+    let bytes = Pair(1, vec![2], 3).to_bytes::<{ num::LE }>();
+    assert_eq!(bytes, [1, 3]);
+    assert_eq!(
+        Pair::from_bytes::<{ num::LE }>(&bytes).unwrap(),
+        Pair(1, vec![], 3)
+    );
+    assert_eq!(
+        <Pair as MaxEncodedSize<{ num::LE }>>::MAX_ENCODED_SIZE,
+        Some(2)
+    );
+
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    enum Event {
+        Click {
+            x: u8,
+            #[databuf(default = "version")]
+            source: u8,
+        },
+        Key(#[databuf(skip)] bool, u8),
+    }
+    let bytes = Event::Click { x: 5, source: 1 }.to_bytes::<{ num::LE }>();
+    assert_eq!(bytes, [0, 5]);
+    assert_eq!(
+        Event::from_bytes::<{ num::LE }>(&bytes).unwrap(),
+        Event::Click { x: 5, source: 7 }
+    );
+    let bytes = Event::Key(true, 3).to_bytes::<{ num::LE }>();
+    assert_eq!(bytes, [1, 3]);
+    assert_eq!(
+        Event::from_bytes::<{ num::LE }>(&bytes).unwrap(),
+        Event::Key(false, 3)
+    );
+
+    // Skipped fields don't end a run of bit fields.
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    struct Flags {
+        #[databuf(bits = 4)]
+        high: u8,
+        #[databuf(skip)]
+        note: String,
+        #[databuf(bits = 4)]
+        low: u8,
+    }
+    let flags = Flags {
+        high: 1,
+        note: "x".into(),
+        low: 2,
+    };
+    let bytes = flags.to_bytes::<{ num::LE }>();
+    assert_eq!(bytes, [0x12]);
+    assert_eq!(
+        Flags::from_bytes::<{ num::LE }>(&bytes).unwrap(),
+        Flags {
+            note: String::new(),
+            ..flags
+        }
+    );
+}
+
+#[test]
+fn test_custom_field_codecs() {
+    use databuf::config::num;
+    use databuf::io;
+
+    /// Encodes seconds as minutes, in a single byte.
+    mod minutes {
+        use databuf::{
+            io::{self, Read, Write},
+            Decode, DecodeFromReader, Encode, Result,
+        };
+
+        pub fn encode<const CONFIG: u16>(
+            secs: &u32,
+            c: &mut (impl Write + ?Sized),
+        ) -> io::Result<()> {
+            ((secs / 60) as u8).encode::<CONFIG>(c)
+        }
+        pub fn decode<const CONFIG: u16>(c: &mut &[u8]) -> Result<u32> {
+            u8::decode::<CONFIG>(c).map(|mins| mins as u32 * 60)
+        }
+        pub fn decode_from_reader<const CONFIG: u16>(r: &mut (impl Read + ?Sized)) -> Result<u32> {
+            u8::decode_from_reader::<CONFIG>(r).map(|mins| mins as u32 * 60)
+        }
+    }
+    fn encode_upper<const CONFIG: u16>(
+        name: &str,
+        c: &mut (impl io::Write + ?Sized),
+    ) -> io::Result<()> {
+        name.to_uppercase().encode::<CONFIG>(c)
+    }
+    fn decode_lower<const CONFIG: u16>(c: &mut &[u8]) -> Result<String> {
+        String::decode::<CONFIG>(c).map(|name| name.to_lowercase())
+    }
+
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    struct Timer {
+        #[databuf(with = "minutes")]
+        duration: u32,
+        #[databuf(encode_with = "encode_upper", decode_with = "decode_lower")]
+        name: String,
+        #[databuf(decode_with = "decode_lower")]
+        label: String,
+        #[databuf(num = BE, with = "minutes")]
+        delay: u32,
+    }
+    let timer = Timer {
+        duration: 120,
+        name: "ab".into(),
+        label: "Cd".into(),
+        delay: 60,
+    };
+    let bytes = timer.to_bytes::<{ num::LE }>();
+    assert_eq!(bytes, [2, 2, b'A', b'B', 2, b'C', b'd', 1]);
+    assert_eq!(timer.encoded_len::<{ num::LE }>(), bytes.len());
+    assert_eq!(
+        Timer::from_bytes::<{ num::LE }>(&bytes).unwrap(),
+        Timer {
+            duration: 120,
+            name: "ab".into(),
+            label: "cd".into(),
+            delay: 60,
+        }
+    );
+    assert_eq!(
+        <Timer as MaxEncodedSize<{ num::LE }>>::MAX_ENCODED_SIZE,
+        None
+    );
+
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    enum Alarm {
+        Once(#[databuf(with = "minutes", from_reader)] u32),
+        Never,
+    }
+    let bytes = Alarm::Once(180).to_bytes::<{ num::LE }>();
+    assert_eq!(bytes, [0, 3]);
+    assert_eq!(
+        Alarm::from_bytes::<{ num::LE }>(&bytes).unwrap(),
+        Alarm::Once(180)
+    );
+    assert_eq!(
+        Alarm::decode_from_reader::<{ num::LE }>(&mut &bytes[..]).unwrap(),
+        Alarm::Once(180)
+    );
+    assert_eq!(
+        Alarm::decode_from_reader::<{ num::LE }>(&mut &[1][..]).unwrap(),
+        Alarm::Never
+    );
+}
+
+#[test]
+fn test_pinned_options() {
+    use databuf::config::{self, float_nan, num};
+
+    // Pinned `$attr = $name` encodes like `config::$attr::$name` added to the config of the caller.
+    macro_rules! check {
+        [$($caller: expr => $($attr: ident = $name: ident: $ty: ty = $value: expr),*;)*] => {$($({
+            #[derive(Encode, Decode, PartialEq, Debug)]
+            struct Pinned {
+                #[databuf($attr = $name)]
+                field: $ty,
+            }
+            let value: $ty = $value;
+            let bytes = Pinned { field: value.clone() }.to_bytes::<{ $caller }>();
+            assert_eq!(bytes, value.to_bytes::<{ $caller | config::$attr::$name }>());
+            assert_eq!(
+                Pinned::from_bytes::<{ $caller }>(&bytes).unwrap().field.to_bytes::<{ $caller | config::$attr::$name }>(),
+                bytes
+            );
+        })*)*};
+    }
+    check! {
+        num::LE =>
+            num = LE: u32 = 0x1234_5678,
+            num = BE: u32 = 0x1234_5678,
+            num = NE: u32 = 0x1234_5678,
+            num = LEB128: u32 = 0x1234_5678,
+            num = BEU62: u32 = 0x1234_5678,
+            num = VLQ: u32 = 0x1234_5678,
+            num = COMPACT_SIZE: u32 = 0x1234_5678,
+            num = SQLITE_VARINT: u32 = 0x1234_5678,
+            num = PREFIX_VARINT: u32 = 0x1234_5678,
+            len = BEU30: String = "a".repeat(300),
+            len = BEU29: String = "a".repeat(300),
+            len = BEU22: String = "a".repeat(300),
+            len = BEU15: String = "a".repeat(300),
+            len = BEU62: String = "a".repeat(300),
+            len = VLQ: String = "a".repeat(300),
+            len = COMPACT_SIZE: String = "a".repeat(300),
+            len = SQLITE_VARINT: String = "a".repeat(300),
+            len = PREFIX_VARINT: String = "a".repeat(300),
+            len = U8: String = "a".repeat(200),
+            len = U16: String = "a".repeat(300),
+            len = U32: String = "a".repeat(300),
+            len = U64: String = "a".repeat(300),
+            len = LEB128: String = "a".repeat(300),
+            char_codec = UTF32: char = '★',
+            char_codec = UTF8: char = '★',
+            float = LE: f32 = 1.5,
+            float = BE: f32 = 1.5,
+            float = F16: f32 = 1.5,
+            float = BF16: f32 = 1.5,
+            float = CANONICAL_NAN: f32 = f32::from_bits(0xFFC0_0001);
+        num::LEB128 =>
+            int_codec = ZIG_ZAG: i32 = -300,
+            int_codec = SIGN_BIT: i32 = -300,
+            int_codec = TWOS_COMPLEMENT: i32 = -300;
+    }
+
+    // Other options of the caller apply to the pinned field, Even if they are not pinned.
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    struct Reading {
+        #[databuf(num = BE)]
+        values: Vec<Option<f32>>,
+    }
+    let reading = Reading {
+        values: vec![Some(f32::from_bits(0xFFC0_0001))],
+    };
+    const CALLER: u16 = num::LE | config::len::U8 | float_nan::CANONICAL;
+    assert_eq!(
+        reading.to_bytes::<CALLER>(),
+        reading
+            .values
+            .to_bytes::<{ num::BE | config::len::U8 | float_nan::CANONICAL }>()
+    );
+}
+
+#[test]
+fn test_with_module_without_reader() {
+    use databuf::config::num::LE;
+
+    /// Encodes a flag as `b'Y'` or `b'N'`, It doesn't decode from reader.
+    mod yes_no {
+        use databuf::{io, Decode, Encode, Result};
+
+        pub fn encode<const CONFIG: u16>(
+            flag: &bool,
+            c: &mut (impl io::Write + ?Sized),
+        ) -> io::Result<()> {
+            (if *flag { b'Y' } else { b'N' }).encode::<CONFIG>(c)
+        }
+        pub fn decode<const CONFIG: u16>(c: &mut &[u8]) -> Result<bool> {
+            u8::decode::<CONFIG>(c).map(|byte| byte == b'Y')
+        }
+    }
+
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    struct Consent {
+        #[databuf(with = "yes_no")]
+        accepted: bool,
+        version: u8,
+    }
+    let consent = Consent {
+        accepted: true,
+        version: 2,
+    };
+    let bytes = consent.to_bytes::<LE>();
+    assert_eq!(bytes, [b'Y', 2]);
+    assert_eq!(Consent::from_bytes::<LE>(&bytes).unwrap(), consent);
+}
+
+#[test]
+fn test_bounds() {
+    use databuf::config::num::LE;
+    use std::marker::PhantomData;
+
+    /// Implements neither `Encode` nor `Decode`.
+    #[derive(PartialEq, Debug)]
+    struct User;
+
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    struct Id<T> {
+        raw: u64,
+        _m: PhantomData<T>,
+    }
+    let id = Id::<User> {
+        raw: 7,
+        _m: PhantomData,
+    };
+    assert_eq!(
+        Id::<User>::from_bytes::<LE>(&id.to_bytes::<LE>()).unwrap(),
+        id
+    );
+
+    trait Schema {
+        type Row;
+    }
+    impl Schema for User {
+        type Row = u16;
+    }
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    struct Table<S: Schema> {
+        rows: Vec<S::Row>,
+        #[databuf(skip)]
+        schema: Option<S>,
+    }
+    let table = Table::<User> {
+        rows: vec![1, 2],
+        schema: Some(User),
+    };
+    let bytes = table.to_bytes::<LE>();
+    assert_eq!(bytes, [2, 1, 0, 2, 0]);
+    assert_eq!(
+        Table::<User>::from_bytes::<LE>(&bytes).unwrap().rows,
+        [1, 2]
+    );
+
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    #[databuf(bound(encode = "K: Encode", decode = "K: Decode<'decode>"))]
+    enum Entry<K, V> {
+        Key(K),
+        Empty(PhantomData<V>),
+    }
+    let entry = Entry::<u8, User>::Key(3);
+    assert_eq!(
+        Entry::<u8, User>::from_bytes::<LE>(&entry.to_bytes::<LE>()).unwrap(),
+        entry
+    );
+
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    struct Pair<A, B> {
+        first: A,
+        #[databuf(bound = "")]
+        second: PhantomData<B>,
+    }
+    let pair = Pair::<u8, User> {
+        first: 1,
+        second: PhantomData,
+    };
+    assert_eq!(
+        Pair::<u8, User>::from_bytes::<LE>(&pair.to_bytes::<LE>()).unwrap(),
+        pair
+    );
+}
+
+#[test]
+fn test_transparent() {
+    use databuf::config::num::{BE, LE};
+    use std::marker::PhantomData;
+
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    #[databuf(transparent)]
+    struct Meters(u32);
+
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    #[databuf(transparent)]
+    struct Tagged<T> {
+        #[databuf(skip)]
+        _tag: PhantomData<T>,
+        value: Vec<u8>,
+    }
+
+    assert_eq!(Meters(5).to_bytes::<BE>(), 5u32.to_bytes::<BE>());
+    assert_eq!(Meters(5).to_bytes::<LEB128>(), [5]);
+    assert_eq!(Meters::from_bytes::<LE>(&[1, 0, 0, 0]).unwrap(), Meters(1));
+    assert_eq!(
+        <Meters as MaxEncodedSize<LE>>::MAX_ENCODED_SIZE,
+        <u32 as MaxEncodedSize<LE>>::MAX_ENCODED_SIZE
+    );
+
+    let tagged = Tagged::<String> {
+        _tag: PhantomData,
+        value: vec![1, 2],
+    };
+    assert_eq!(tagged.to_bytes::<LE>(), vec![1u8, 2].to_bytes::<LE>());
+    assert_eq!(
+        Tagged::<String>::from_bytes::<LE>(&[2, 1, 2]).unwrap(),
+        tagged
+    );
+}
@@ -0,0 +1,45 @@
+use databuf::{config::num::BE, io::ErrorKind, *};
+
+#[derive(Encode, Decode, PartialEq, Debug)]
+struct Header {
+    id: u32,
+    flags: [bool; 2],
+    name: String,
+}
+
+#[test]
+fn test_encode_into_slice() {
+    let header = Header {
+        id: 0xC0DE,
+        flags: [true, false],
+        name: "databuf".into(),
+    };
+
+    let mut buf = [0; 16];
+    let mut writer = buf.as_mut_slice();
+    header.encode::<BE>(&mut writer).unwrap();
+    let amt = 16 - writer.len();
+    assert_eq!(amt, 4 + 2 + 1 + 7);
+    assert_eq!(buf[..amt], header.to_bytes::<BE>());
+
+    let mut buf = [0; 8];
+    let err = header.encode::<BE>(&mut buf.as_mut_slice()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WriteZero);
+}
+
+#[test]
+fn test_decode_from_slice_reader() {
+    let header = Header {
+        id: 42,
+        flags: [false, true],
+        name: "no_std".into(),
+    };
+    let bytes = header.to_bytes::<BE>();
+
+    let mut reader = bytes.as_slice();
+    assert_eq!(
+        Header::decode_from_reader::<BE>(&mut reader).unwrap(),
+        header
+    );
+    assert!(reader.is_empty());
+}
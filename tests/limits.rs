@@ -0,0 +1,131 @@
+use databuf::{
+    config::num::LE,
+    error::{ErrorKind, Limit},
+    limits::Budget,
+    *,
+};
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+enum Tree {
+    Leaf(u8),
+    Node(Box<Tree>, Box<Tree>),
+}
+
+fn tree(depth: u8) -> Tree {
+    match depth {
+        0 => Tree::Leaf(0),
+        _ => Tree::Node(Box::new(tree(depth - 1)), Box::new(Tree::Leaf(depth))),
+    }
+}
+
+fn limit_of(err: error::DecodeError) -> Limit {
+    match err.kind() {
+        ErrorKind::LimitExceeded(limit) => *limit,
+        kind => panic!("unexpected error: {kind}"),
+    }
+}
+
+#[test]
+fn test_huge_length_prefix() {
+    // BEU30 length of `0x3FFF_FFFF` elements, without the elements.
+    let bytes = [0x7F, 0xFF, 0xFF, 0xFF];
+    let err = Vec::<u64>::from_bytes::<LE>(&bytes).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InsufficientBytes { .. }));
+
+    let err = String::decode_from_reader::<LE>(&mut &bytes[..]).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InsufficientBytes { .. }));
+}
+
+#[test]
+fn test_limits() {
+    let limits = Limits {
+        max_len: 4,
+        max_str_len: 8,
+        // the outer `Vec` and two inner `Vec<u64>` of 4 elements.
+        max_alloc: size_of::<[Vec<u64>; 3]>() + 2 * 32,
+        max_depth: 8,
+        max_frame: usize::MAX,
+    };
+    let decode = |bytes: &[u8]| Vec::<u32>::from_bytes_with_limits::<LE>(bytes, &limits);
+    assert_eq!(decode(&[1, 1, 0, 0, 0]).unwrap(), [1]);
+    assert_eq!(limit_of(decode(&[5]).unwrap_err()), Limit::Len);
+
+    let bytes = ["Hello, World!"; 1].to_bytes::<LE>();
+    let err = <[String; 1]>::from_bytes_with_limits::<LE>(&bytes, &limits).unwrap_err();
+    assert_eq!(limit_of(err), Limit::StrLen);
+    let err = <[&str; 1]>::from_bytes_with_limits::<LE>(&bytes, &limits).unwrap_err();
+    assert_eq!(limit_of(err), Limit::StrLen);
+
+    let bytes = vec![vec![0_u64; 4]; 3].to_bytes::<LE>();
+    let err = Vec::<Vec<u64>>::from_bytes_with_limits::<LE>(&bytes, &limits).unwrap_err();
+    assert_eq!(err.path().unwrap().to_string(), "[2]");
+    assert_eq!(limit_of(err), Limit::Alloc);
+
+    let bytes = tree(7).to_bytes::<LE>();
+    assert_eq!(
+        Tree::from_bytes_with_limits::<LE>(&bytes, &limits).unwrap(),
+        tree(7)
+    );
+    let bytes = tree(8).to_bytes::<LE>();
+    let err = Tree::from_bytes_with_limits::<LE>(&bytes, &limits).unwrap_err();
+    assert_eq!(limit_of(err), Limit::Depth);
+
+    // The length of NUL-terminated strings is checked while reading.
+    let err = text::NulTerminated::decode_from_reader_with_limits::<LE>(
+        &mut std::io::repeat(b'a'),
+        &limits,
+    )
+    .unwrap_err();
+    assert_eq!(limit_of(err), Limit::StrLen);
+
+    // Limits are only applied to the decode operations they are passed to.
+    assert_eq!(Tree::from_bytes::<LE>(&bytes).unwrap(), tree(8));
+    let err = Tree::decode_from_reader_with_limits::<LE>(&mut &bytes[..], &limits).unwrap_err();
+    assert_eq!(limit_of(err), Limit::Depth);
+}
+
+#[test]
+fn test_shared_budget() {
+    let limits = Limits {
+        max_alloc: 4 * 32,
+        max_depth: 8,
+        ..Limits::default()
+    };
+    let mut budget = Budget::new(limits);
+
+    // The depth is restored after each value, But allocations add up.
+    let bytes = tree(7).to_bytes::<LE>();
+    for _ in 0..2 {
+        let tree = Tree::decode_limited::<LE>(&mut &bytes[..], &mut budget).unwrap();
+        assert_eq!(tree, self::tree(7));
+    }
+    let bytes = vec![0_u64; 4].to_bytes::<LE>();
+    for _ in 0..4 {
+        Vec::<u64>::decode_from_reader_limited::<LE>(&mut &bytes[..], &mut budget).unwrap();
+    }
+    let err = Vec::<u64>::decode_limited::<LE>(&mut &bytes[..], &mut budget).unwrap_err();
+    assert_eq!(limit_of(err), Limit::Alloc);
+}
+
+#[test]
+fn test_manual_decode_impl() {
+    /// Implements only `Decode::decode`, Like impls written before `Budget`.
+    #[derive(Debug, PartialEq)]
+    struct Celsius(i16);
+    impl Decode<'_> for Celsius {
+        fn decode<const CONFIG: u16>(c: &mut &[u8]) -> Result<Self> {
+            i16::decode::<CONFIG>(c).map(Celsius)
+        }
+    }
+
+    let limits = Limits {
+        max_len: 2,
+        ..Limits::default()
+    };
+    let temps =
+        Vec::<Celsius>::from_bytes_with_limits::<LE>(&[2, 0xFE, 0xFF, 1, 0], &limits).unwrap();
+    assert_eq!(temps, [Celsius(-2), Celsius(1)]);
+    let err =
+        Vec::<Celsius>::from_bytes_with_limits::<LE>(&[3, 0, 0, 0, 0, 0, 0], &limits).unwrap_err();
+    assert_eq!(limit_of(err), Limit::Len);
+}
@@ -1,8 +1,8 @@
-use databuf::error::IntegerOverflow;
+use databuf::error::{ErrorKind, IntegerOverflow};
 use databuf::var_int::*;
 use databuf::{
     config::num::{LE, LEB128},
-    Decode, Encode,
+    Decode, DecodeFromReader, Encode, MaxEncodedSize,
 };
 
 #[test]
@@ -11,6 +11,7 @@ fn test_leb128() {
         num: impl Encode + for<'a> Decode<'a> + std::cmp::PartialEq + std::fmt::Debug,
     ) -> Vec<u8> {
         let bytes = num.to_bytes::<CONFIG>();
+        assert_eq!(num.encoded_len::<CONFIG>(), bytes.len());
         let new_num = Decode::from_bytes::<CONFIG>(&bytes).unwrap();
         assert_eq!(num, new_num);
         bytes
@@ -41,7 +42,7 @@ fn test_leb128() {
     {
         let bytes = (num.into() + 1).to_bytes::<LEB128>();
         let err = T::from_bytes::<LEB128>(&bytes).unwrap_err();
-        assert!(err.is::<IntegerOverflow>());
+        assert!(matches!(err.kind(), ErrorKind::IntegerOverflow));
     }
     check_overflow::<u16>(u16::MAX);
     check_overflow::<u32>(u32::MAX);
@@ -50,13 +51,14 @@ fn test_leb128() {
     let mut bytes = vec![255; 18];
     bytes.push(0b111_u8);
     let err = u128::from_bytes::<LEB128>(&bytes).unwrap_err();
-    assert!(err.is::<IntegerOverflow>());
+    assert!(matches!(err.kind(), ErrorKind::IntegerOverflow));
 }
 
 macro_rules! assert_varint {
     [$len: expr, $expect: expr] => {
         let bytes = $len.to_bytes::<LE>();
         assert_eq!(bytes, $expect);
+        assert_eq!($len.encoded_len::<LE>(), bytes.len());
         assert_eq!($len, Decode::from_bytes::<LE>(&bytes).unwrap());
     };
 }
@@ -112,6 +114,336 @@ fn test_be_u30() {
     assert_varint!(BEU30(1073741823), [255, 255, 255, 255]);
 }
 
+#[test]
+fn test_be_u62() {
+    assert_varint!(BEU62(0), [0]);
+    assert_varint!(BEU62(37), [0x25]);
+    assert_varint!(BEU62(63), [63]);
+
+    assert_varint!(BEU62(64), [0x40, 64]);
+    assert_varint!(BEU62(15293), [0x7B, 0xBD]);
+
+    assert_varint!(BEU62(16384), [0x80, 0, 0x40, 0]);
+    assert_varint!(BEU62(494878333), [0x9D, 0x7F, 0x3E, 0x7D]);
+
+    assert_varint!(BEU62(1073741824), [0xC0, 0, 0, 0, 0x40, 0, 0, 0]);
+    assert_varint!(
+        BEU62(151288809941952652),
+        [0xC2, 0x19, 0x7C, 0x5E, 0xFF, 0x14, 0xE8, 0x8C]
+    );
+    assert_varint!(BEU62(BEU62::MAX), [0xFF; 8]);
+    for num in [BEU62::MAX + 1, u64::MAX] {
+        let err = BEU62(num).encode::<LE>(&mut Vec::new()).unwrap_err();
+        assert!(err.get_ref().unwrap().is::<IntegerOverflow>());
+    }
+
+    // non-minimal encoding
+    assert_eq!(BEU62::from_bytes::<LE>(&[0x40, 0x25]).unwrap(), BEU62(37));
+}
+
+#[test]
+fn test_beu62_num() {
+    use databuf::config::{len, num};
+    const CONFIG: u16 = num::BEU62 | len::BEU62;
+
+    fn check<T>(num: T, expect: &[u8])
+    where
+        T: Encode + for<'de> Decode<'de> + DecodeFromReader + PartialEq + std::fmt::Debug,
+    {
+        let bytes = num.to_bytes::<CONFIG>();
+        assert_eq!(bytes, expect);
+        assert_eq!(num.encoded_len::<CONFIG>(), bytes.len());
+        assert_eq!(T::from_bytes::<CONFIG>(&bytes).unwrap(), num);
+    }
+    check(37_u16, &[0x25]);
+    check(15293_u32, &[0x7B, 0xBD]);
+    check(494878333_u64, &[0x9D, 0x7F, 0x3E, 0x7D]);
+    check(
+        151288809941952652_u128,
+        &[0xC2, 0x19, 0x7C, 0x5E, 0xFF, 0x14, 0xE8, 0x8C],
+    );
+    check(BEU62::MAX as usize, &[0xFF; 8]);
+    check(-1_i32, &[1]);
+    check(1_i64, &[2]);
+    check(i16::MIN, &[0x80, 0, 0xFF, 0xFF]);
+    check(1.5_f32, &1.5_f32.to_be_bytes());
+    check(b'a', b"a");
+    check(vec![1_u16; 64], &[[0x40, 64].as_slice(), &[1; 64]].concat());
+
+    for num in [1_u64 << 62, u64::MAX] {
+        let err = num.encode::<CONFIG>(&mut Vec::new()).unwrap_err();
+        assert!(err.get_ref().unwrap().is::<IntegerOverflow>());
+    }
+    assert!(i64::MIN.encode::<CONFIG>(&mut Vec::new()).is_err());
+
+    let err = u16::from_bytes::<CONFIG>(&[0x80, 1, 0, 0]).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::IntegerOverflow));
+    let err = u32::from_bytes::<CONFIG>(&[0xC0, 0, 0, 0]).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::InsufficientBytes { missing: Some(4) }
+    ));
+
+    assert_eq!(<u16 as MaxEncodedSize<CONFIG>>::MAX_ENCODED_SIZE, Some(4));
+    assert_eq!(<i32 as MaxEncodedSize<CONFIG>>::MAX_ENCODED_SIZE, Some(8));
+    assert_eq!(<f64 as MaxEncodedSize<CONFIG>>::MAX_ENCODED_SIZE, Some(8));
+}
+
+fn non_canonical<T: for<'de> Decode<'de> + std::fmt::Debug>(bytes: &[u8]) -> bool {
+    matches!(
+        T::from_bytes::<LE>(bytes).unwrap_err().kind(),
+        ErrorKind::NonCanonical
+    )
+}
+
+#[test]
+fn test_vlq() {
+    assert_varint!(VLQ(0), [0]);
+    assert_varint!(VLQ(0x40), [0x40]);
+    assert_varint!(VLQ(0x7F), [0x7F]);
+    assert_varint!(VLQ(0x80), [0x81, 0]);
+    assert_varint!(VLQ(0x2000), [0xC0, 0]);
+    assert_varint!(VLQ(0x3FFF), [0xFF, 0x7F]);
+    assert_varint!(VLQ(0x4000), [0x81, 0x80, 0]);
+    assert_varint!(VLQ(0x1F_FFFF), [0xFF, 0xFF, 0x7F]);
+    assert_varint!(VLQ(0x0FFF_FFFF), [0xFF, 0xFF, 0xFF, 0x7F]);
+    assert_varint!(
+        VLQ(u64::MAX),
+        [0x81, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F]
+    );
+
+    assert!(non_canonical::<VLQ>(&[0x80, 0x7F]));
+    assert!(non_canonical::<VLQ>(&[0x80; 64]));
+    let err = VLQ::from_bytes::<LE>(&[0x82, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0]);
+    assert!(matches!(
+        err.unwrap_err().kind(),
+        ErrorKind::IntegerOverflow
+    ));
+}
+
+#[test]
+fn test_compact_size() {
+    assert_varint!(CompactSize(0), [0]);
+    assert_varint!(CompactSize(0xFC), [0xFC]);
+    assert_varint!(CompactSize(0xFD), [0xFD, 0xFD, 0]);
+    assert_varint!(CompactSize(0xFFFF), [0xFD, 0xFF, 0xFF]);
+    assert_varint!(CompactSize(0x1_0000), [0xFE, 0, 0, 1, 0]);
+    assert_varint!(CompactSize(0xFFFF_FFFF), [0xFE, 0xFF, 0xFF, 0xFF, 0xFF]);
+    assert_varint!(CompactSize(0x1_0000_0000), [0xFF, 0, 0, 0, 0, 1, 0, 0, 0]);
+    assert_varint!(CompactSize(u64::MAX), [0xFF; 9]);
+
+    assert!(non_canonical::<CompactSize>(&[0xFD, 0xFC, 0]));
+    assert!(non_canonical::<CompactSize>(&[0xFE, 0xFF, 0xFF, 0, 0]));
+    assert!(non_canonical::<CompactSize>(&[
+        0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0
+    ]));
+}
+
+#[test]
+fn test_sqlite_varint() {
+    assert_varint!(SqliteVarint(0), [0]);
+    assert_varint!(SqliteVarint(0x7F), [0x7F]);
+    assert_varint!(SqliteVarint(0x80), [0x81, 0]);
+    assert_varint!(SqliteVarint(0x3FFF), [0xFF, 0x7F]);
+    assert_varint!(
+        SqliteVarint((1 << 56) - 1),
+        [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F]
+    );
+    assert_varint!(
+        SqliteVarint(1 << 56),
+        [0x80, 0xC0, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0]
+    );
+    assert_varint!(SqliteVarint(u64::MAX), [0xFF; 9]);
+
+    assert!(non_canonical::<SqliteVarint>(&[0x80, 0x7F]));
+    assert!(non_canonical::<SqliteVarint>(&[
+        0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0xFF
+    ]));
+}
+
+#[test]
+fn test_prefix_varint() {
+    assert_varint!(PrefixVarint(0), [1]);
+    assert_varint!(PrefixVarint(0x7F), [0xFF]);
+    assert_varint!(PrefixVarint(0x80), [0x02, 0x02]);
+    assert_varint!(PrefixVarint(0x3FFF), [0xFE, 0xFF]);
+    assert_varint!(PrefixVarint(0x4000), [0x04, 0, 0x02]);
+    assert_varint!(
+        PrefixVarint((1 << 56) - 1),
+        [0x80, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+    );
+    assert_varint!(PrefixVarint(1 << 56), [0, 0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_varint!(
+        PrefixVarint(u64::MAX),
+        [0; 1].into_iter().chain([0xFF; 8]).collect::<Vec<_>>()
+    );
+
+    assert!(non_canonical::<PrefixVarint>(&[0x02, 0]));
+    assert!(non_canonical::<PrefixVarint>(&[
+        0, 0x7F, 0, 0, 0, 0, 0, 0, 0
+    ]));
+}
+
+#[test]
+fn test_varint_num() {
+    use databuf::config::{len, num};
+
+    fn check<const CONFIG: u16, T>(num: T, expect: &[u8])
+    where
+        T: Encode + for<'de> Decode<'de> + DecodeFromReader + PartialEq + std::fmt::Debug,
+    {
+        let bytes = num.to_bytes::<CONFIG>();
+        assert_eq!(bytes, expect);
+        assert_eq!(num.encoded_len::<CONFIG>(), bytes.len());
+        assert_eq!(T::from_bytes::<CONFIG>(&bytes).unwrap(), num);
+        assert_eq!(
+            T::decode_from_reader::<CONFIG>(&mut &bytes[..]).unwrap(),
+            num
+        );
+    }
+    const VLQ_CONFIG: u16 = num::VLQ | len::VLQ;
+    check::<VLQ_CONFIG, _>(0x80_u32, &[0x81, 0]);
+    check::<VLQ_CONFIG, _>(-1_i16, &[1]);
+    check::<VLQ_CONFIG, _>(1.5_f32, &1.5_f32.to_be_bytes());
+    check::<VLQ_CONFIG, _>(vec![1_u8; 128], &[[0x81, 0].as_slice(), &[1; 128]].concat());
+
+    const COMPACT_SIZE_CONFIG: u16 = num::COMPACT_SIZE | len::COMPACT_SIZE;
+    check::<COMPACT_SIZE_CONFIG, _>(0xFD_u16, &[0xFD, 0xFD, 0]);
+    check::<COMPACT_SIZE_CONFIG, _>(-1_i64, &[1]);
+    check::<COMPACT_SIZE_CONFIG, _>(1.5_f64, &1.5_f64.to_le_bytes());
+    check::<COMPACT_SIZE_CONFIG, _>(
+        "a".repeat(253),
+        &[[0xFD, 0xFD, 0].as_slice(), "a".repeat(253).as_bytes()].concat(),
+    );
+
+    const SQLITE_VARINT_CONFIG: u16 = num::SQLITE_VARINT | len::SQLITE_VARINT;
+    check::<SQLITE_VARINT_CONFIG, _>(u64::MAX, &[0xFF; 9]);
+    check::<SQLITE_VARINT_CONFIG, _>(i8::MIN, &[0x81, 0x7F]);
+    check::<SQLITE_VARINT_CONFIG, _>(vec![true; 2], &[2, 1, 1]);
+
+    const PREFIX_VARINT_CONFIG: u16 = num::PREFIX_VARINT | len::PREFIX_VARINT;
+    check::<PREFIX_VARINT_CONFIG, _>(0x80_u128, &[0x02, 0x02]);
+    check::<PREFIX_VARINT_CONFIG, _>(i32::MIN, &[0xF0, 0xFF, 0xFF, 0xFF, 0x1F]);
+    check::<PREFIX_VARINT_CONFIG, _>(1.5_f32, &1.5_f32.to_le_bytes());
+    check::<PREFIX_VARINT_CONFIG, _>(String::from("Hi"), &[0x05, b'H', b'i']);
+
+    let err = u16::from_bytes::<VLQ_CONFIG>(&[0x84, 0x80, 0]).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::IntegerOverflow));
+    let err = u16::from_bytes::<COMPACT_SIZE_CONFIG>(&[0xFD, 1, 0]).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::NonCanonical));
+    let err = String::from_bytes::<PREFIX_VARINT_CONFIG>(&[0x02, 0]).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::NonCanonical));
+    let err = u128::MAX
+        .encode::<SQLITE_VARINT_CONFIG>(&mut Vec::new())
+        .unwrap_err();
+    assert!(err.get_ref().unwrap().is::<IntegerOverflow>());
+
+    assert_eq!(
+        <u16 as MaxEncodedSize<VLQ_CONFIG>>::MAX_ENCODED_SIZE,
+        Some(3)
+    );
+    assert_eq!(
+        <u64 as MaxEncodedSize<VLQ_CONFIG>>::MAX_ENCODED_SIZE,
+        Some(10)
+    );
+    assert_eq!(
+        <u16 as MaxEncodedSize<COMPACT_SIZE_CONFIG>>::MAX_ENCODED_SIZE,
+        Some(3)
+    );
+    assert_eq!(
+        <i32 as MaxEncodedSize<COMPACT_SIZE_CONFIG>>::MAX_ENCODED_SIZE,
+        Some(5)
+    );
+    assert_eq!(
+        <u64 as MaxEncodedSize<SQLITE_VARINT_CONFIG>>::MAX_ENCODED_SIZE,
+        Some(9)
+    );
+    assert_eq!(
+        <u32 as MaxEncodedSize<PREFIX_VARINT_CONFIG>>::MAX_ENCODED_SIZE,
+        Some(5)
+    );
+}
+
+#[test]
+fn test_int_codec() {
+    use databuf::config::{int_codec, num};
+
+    fn check<const CONFIG: u16, T>(num: T, expect: &[u8])
+    where
+        T: Encode + for<'de> Decode<'de> + DecodeFromReader + PartialEq + std::fmt::Debug,
+    {
+        let bytes = num.to_bytes::<CONFIG>();
+        assert_eq!(bytes, expect);
+        assert_eq!(num.encoded_len::<CONFIG>(), bytes.len());
+        assert_eq!(T::from_bytes::<CONFIG>(&bytes).unwrap(), num);
+        assert_eq!(
+            T::decode_from_reader::<CONFIG>(&mut &bytes[..]).unwrap(),
+            num
+        );
+    }
+    fn overflow<const CONFIG: u16, T: for<'de> Decode<'de> + std::fmt::Debug>(
+        bytes: &[u8],
+    ) -> bool {
+        let err = T::from_bytes::<CONFIG>(bytes).unwrap_err();
+        matches!(err.kind(), ErrorKind::IntegerOverflow)
+    }
+
+    // `i8` is encoded with the number encoding too.
+    check::<LE, _>(i8::MIN, &[0x80]);
+    check::<LEB128, _>(-1_i8, &[1]);
+    check::<LEB128, _>(i8::MIN, &[0xFF, 1]);
+
+    const SLEB128: u16 = num::LEB128 | int_codec::TWOS_COMPLEMENT;
+    check::<SLEB128, _>(2_i32, &[2]);
+    check::<SLEB128, _>(-2_i64, &[0x7E]);
+    check::<SLEB128, _>(127_i16, &[0xFF, 0]);
+    check::<SLEB128, _>(-127_i16, &[0x81, 0x7F]);
+    check::<SLEB128, _>(128_i32, &[0x80, 1]);
+    check::<SLEB128, _>(-128_i32, &[0x80, 0x7F]);
+    check::<SLEB128, _>(-129_isize, &[0xFF, 0x7E]);
+    check::<SLEB128, _>(-1_i8, &[0x7F]);
+    check::<SLEB128, _>(i8::MIN, &[0x80, 0x7F]);
+    check::<SLEB128, _>(-123456_i32, &[0xC0, 0xBB, 0x78]);
+    check::<SLEB128, _>(i128::MIN, &[[0x80; 18].as_slice(), &[0x7E]].concat());
+    check::<SLEB128, _>(i128::MAX, &[[0xFF; 18].as_slice(), &[0x01]].concat());
+    assert!(overflow::<SLEB128, i8>(&[0x80, 1]));
+    assert!(overflow::<SLEB128, i8>(&[0xFF, 0x7E]));
+    assert!(overflow::<SLEB128, i128>(
+        &[[0x80; 18].as_slice(), &[0x02]].concat()
+    ));
+    assert!(overflow::<SLEB128, i128>(&[0x80; 19]));
+    assert_eq!(<i32 as MaxEncodedSize<SLEB128>>::MAX_ENCODED_SIZE, Some(5));
+
+    const SIGN_BIT: u16 = num::LEB128 | int_codec::SIGN_BIT;
+    check::<SIGN_BIT, _>(1_i32, &[2]);
+    check::<SIGN_BIT, _>(-1_i32, &[3]);
+    check::<SIGN_BIT, _>(i8::MIN, &[0x81, 2]);
+    check::<SIGN_BIT, _>(i16::MIN, &[0x81, 0x80, 4]);
+    check::<SIGN_BIT, _>(i128::MAX, &i128::MAX.to_bytes::<SIGN_BIT>());
+    assert_eq!(i32::from_bytes::<SIGN_BIT>(&[1]).unwrap(), 0);
+    assert!(overflow::<SIGN_BIT, i8>(&[0x83, 2]));
+    let err = i128::MIN.encode::<SIGN_BIT>(&mut Vec::new()).unwrap_err();
+    assert!(err.get_ref().unwrap().is::<IntegerOverflow>());
+    assert_eq!(<i16 as MaxEncodedSize<SIGN_BIT>>::MAX_ENCODED_SIZE, Some(3));
+    assert_eq!(
+        <i64 as MaxEncodedSize<SIGN_BIT>>::MAX_ENCODED_SIZE,
+        Some(10)
+    );
+
+    const VLQ_TWOS: u16 = num::VLQ | int_codec::TWOS_COMPLEMENT;
+    check::<VLQ_TWOS, _>(-1_i32, &[0x8F, 0xFF, 0xFF, 0xFF, 0x7F]);
+    check::<VLQ_TWOS, _>(
+        i64::MIN,
+        &[0x81, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0],
+    );
+    const BEU62_TWOS: u16 = num::BEU62 | int_codec::TWOS_COMPLEMENT;
+    check::<BEU62_TWOS, _>(-1_i8, &[0x40, 0xFF]);
+    check::<BEU62_TWOS, _>(5_i16, &[5]);
+    assert!(overflow::<BEU62_TWOS, i8>(&[0x41, 0]));
+    let err = (-1_i64).encode::<BEU62_TWOS>(&mut Vec::new()).unwrap_err();
+    assert!(err.get_ref().unwrap().is::<IntegerOverflow>());
+}
+
 #[test]
 fn test_scaler_type() {
     for word in [0x_A5C11, 0x_C0DE, 0x_DEC0DE, 0x_ADDED, 0x_AB0DE, 0x_CAFE] {
@@ -120,7 +452,7 @@ fn test_scaler_type() {
     }
     for word in [
         0x_DEAD_BEEF,
-        0x000F_aded_Face,
+        0x000F_ADED_FACE,
         0x0BAD_F00D,
         0x00C0_1DC0_FFEE,
         0x_C0CA_C01A,
@@ -129,3 +461,129 @@ fn test_scaler_type() {
         assert_eq!(word, u64::from_bytes::<LE>(&bytes).unwrap());
     }
 }
+
+#[test]
+fn test_float() {
+    use databuf::config::{float, num};
+
+    fn check<const CONFIG: u16, T>(num: T, expect: &[u8])
+    where
+        T: Encode
+            + for<'de> Decode<'de>
+            + DecodeFromReader
+            + MaxEncodedSize<CONFIG>
+            + PartialEq
+            + std::fmt::Debug,
+    {
+        let bytes = num.to_bytes::<CONFIG>();
+        assert_eq!(bytes, expect);
+        assert_eq!(num.encoded_len::<CONFIG>(), bytes.len());
+        assert_eq!(T::MAX_ENCODED_SIZE, Some(bytes.len()));
+        assert_eq!(T::from_bytes::<CONFIG>(&bytes).unwrap(), num);
+        assert_eq!(
+            T::decode_from_reader::<CONFIG>(&mut &bytes[..]).unwrap(),
+            num
+        );
+    }
+
+    // Byte order follows the `num` option, Unless it is set.
+    check::<LEB128, _>(1.5_f32, &1.5_f32.to_le_bytes());
+    check::<{ num::LEB128 | float::BE }, _>(1.5_f32, &1.5_f32.to_be_bytes());
+    check::<{ num::BE | float::LE }, _>(-2.25_f64, &(-2.25_f64).to_le_bytes());
+    check::<{ num::VLQ | float::LE }, _>(1.5_f32, &1.5_f32.to_le_bytes());
+
+    const F16: u16 = num::BE | float::F16;
+    check::<F16, _>(1.0_f32, &[0x3C, 0]);
+    check::<F16, _>(-2.0_f32, &[0xC0, 0]);
+    check::<F16, _>(65504.0_f32, &[0x7B, 0xFF]);
+    check::<F16, _>(f32::INFINITY, &[0x7C, 0]);
+    check::<F16, _>(f32::NEG_INFINITY, &[0xFC, 0]);
+    // Smallest subnormal: 2^-24
+    check::<F16, _>(5.960_464_5e-8_f32, &[0, 1]);
+    check::<{ num::LE | float::F16 }, _>(0.5_f32, &[0, 0x38]);
+    // f64 is not affected.
+    check::<F16, _>(1.5_f64, &1.5_f64.to_be_bytes());
+
+    // Rounded to the nearest, Ties to even.
+    assert_eq!(1.000_25_f32.to_bytes::<F16>(), [0x3C, 0]);
+    assert_eq!(1.000_8_f32.to_bytes::<F16>(), [0x3C, 1]);
+    assert_eq!(65520.0_f32.to_bytes::<F16>(), [0x7C, 0]);
+    assert_eq!(1e-10_f32.to_bytes::<F16>(), [0, 0]);
+    assert!(f32::from_bytes::<F16>(&[0x7E, 0]).unwrap().is_nan());
+
+    const BF16: u16 = num::BE | float::BF16;
+    check::<BF16, _>(1.5_f32, &[0x3F, 0xC0]);
+    check::<BF16, _>(-1024.0_f32, &[0xC4, 0x80]);
+    check::<BF16, _>(f32::INFINITY, &[0x7F, 0x80]);
+    assert_eq!(1.003_f32.to_bytes::<BF16>(), [0x3F, 0x80]);
+    assert!(f32::from_bytes::<BF16>(&f32::NAN.to_bytes::<BF16>())
+        .unwrap()
+        .is_nan());
+
+    // NaN canonicalization
+    let nan = f32::from_bits(0xFFC0_0001);
+    assert_eq!(nan.to_bytes::<LE>(), 0xFFC0_0001_u32.to_le_bytes());
+    const CANONICAL: u16 = num::LE | float::CANONICAL_NAN;
+    assert_eq!(nan.to_bytes::<CANONICAL>(), f32::NAN.to_le_bytes());
+    assert_eq!(
+        f64::from_bits(0x7FF0_0000_0000_0001).to_bytes::<CANONICAL>(),
+        f64::NAN.to_le_bytes()
+    );
+    assert_eq!(
+        nan.to_bytes::<{ CANONICAL | float::F16 }>(),
+        f32::NAN.to_bytes::<{ num::LE | float::F16 }>()
+    );
+    check::<CANONICAL, _>(-0.0_f64, &(-0.0_f64).to_le_bytes());
+}
+
+#[test]
+fn test_char_codec() {
+    use databuf::config::{char_codec::UTF8, num};
+
+    fn check<const CONFIG: u16>(ch: char, expect: &[u8]) {
+        let bytes = ch.to_bytes::<CONFIG>();
+        assert_eq!(bytes, expect);
+        assert_eq!(ch.encoded_len::<CONFIG>(), bytes.len());
+        assert_eq!(char::from_bytes::<CONFIG>(&bytes).unwrap(), ch);
+        assert_eq!(
+            char::decode_from_reader::<CONFIG>(&mut &bytes[..]).unwrap(),
+            ch
+        );
+    }
+    fn invalid<const CONFIG: u16>(bytes: &[u8]) -> u32 {
+        let err = char::from_bytes::<CONFIG>(bytes).unwrap_err();
+        let ErrorKind::InvalidChar(num) = *err.kind() else {
+            panic!("{err:?}")
+        };
+        let err = char::decode_from_reader::<CONFIG>(&mut &bytes[..]).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::InvalidChar(n) if *n == num));
+        num
+    }
+
+    check::<LE>('é', &[0xE9, 0, 0, 0]);
+    check::<{ num::LE | UTF8 }>('A', b"A");
+    check::<{ num::LE | UTF8 }>('é', "é".as_bytes());
+    check::<{ num::BE | UTF8 }>('€', "€".as_bytes());
+    check::<{ num::LEB128 | UTF8 }>('\u{10FFFF}', "\u{10FFFF}".as_bytes());
+    check::<{ num::BEU62 | UTF8 }>('😀', "😀".as_bytes());
+    assert_eq!(
+        <char as MaxEncodedSize<{ num::LE | UTF8 }>>::MAX_ENCODED_SIZE,
+        Some(4)
+    );
+
+    const U: u16 = num::LE | UTF8;
+    // Surrogate, Overlong, Out of range, Bad continuation and lead bytes.
+    assert_eq!(invalid::<U>(&[0xED, 0xA0, 0x80]), 0xED_A080);
+    assert_eq!(invalid::<U>(&[0xC0, 0x80]), 0xC080);
+    assert_eq!(invalid::<U>(&[0xF4, 0x90, 0x80, 0x80]), 0xF490_8080);
+    assert_eq!(invalid::<U>(&[0xE2, 0x41]), 0xE241);
+    assert_eq!(invalid::<U>(&[0x80]), 0x80);
+    assert_eq!(invalid::<U>(&[0xFF]), 0xFF);
+    let err = char::from_bytes::<U>(&[0xE2, 0x82]).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InsufficientBytes { .. }));
+
+    let text: Vec<char> = "héllo €".chars().collect();
+    let bytes = text.to_bytes::<U>();
+    assert_eq!(bytes[1..], *"héllo €".as_bytes());
+    assert_eq!(Vec::<char>::from_bytes::<U>(&bytes).unwrap(), text);
+}
@@ -0,0 +1,116 @@
+use databuf::{
+    config::{len, num},
+    error::{ErrorKind, InteriorNul, InvalidChar},
+    text::*,
+    *,
+};
+
+fn check<const CONFIG: u16, T>(text: &T, expect: &[u8])
+where
+    T: Encode + for<'de> Decode<'de> + DecodeFromReader + PartialEq + std::fmt::Debug,
+{
+    let bytes = text.to_bytes::<CONFIG>();
+    assert_eq!(bytes, expect);
+    assert_eq!(text.encoded_len::<CONFIG>(), bytes.len());
+    assert_eq!(T::from_bytes_exact::<CONFIG>(&bytes).unwrap(), *text);
+    assert_eq!(
+        T::decode_from_reader::<CONFIG>(&mut &bytes[..]).unwrap(),
+        *text
+    );
+}
+
+#[test]
+fn test_nul_terminated() {
+    const LE: u16 = num::LE;
+    check::<LE, _>(&NulTerminated::from("abc"), b"abc\0");
+    check::<LE, _>(&NulTerminated::from(""), &[0]);
+    check::<LE, _>(&NulTerminated::from("é"), &[0xC3, 0xA9, 0]);
+
+    let mut bytes: &[u8] = b"one\0two\0";
+    assert_eq!(NulTerminated::decode::<LE>(&mut bytes).unwrap().0, "one");
+    assert_eq!(NulTerminated::decode::<LE>(&mut bytes).unwrap().0, "two");
+    assert!(bytes.is_empty());
+
+    let err = NulTerminated::from_bytes::<LE>(b"abc").unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InsufficientBytes { .. }));
+    let err = NulTerminated::decode_from_reader::<LE>(&mut &b"abc"[..]).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InsufficientBytes { .. }));
+    let err = NulTerminated::from_bytes::<LE>(&[b'a', 0xFF, 0]).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::InvalidUtf8 { valid_up_to: 1, .. }
+    ));
+
+    let err = NulTerminated::from("a\0b")
+        .encode::<LE>(&mut Vec::new())
+        .unwrap_err();
+    assert!(err.get_ref().unwrap().is::<InteriorNul>());
+}
+
+#[test]
+fn test_utf16() {
+    const LE: u16 = num::LE;
+    check::<LE, _>(&Utf16LE::from("Hi"), &[2, b'H', 0, b'i', 0]);
+    check::<LE, _>(&Utf16BE::from("Hi"), &[2, 0, b'H', 0, b'i']);
+    // Length is the number of code units.
+    check::<LE, _>(&Utf16LE::from("😀"), &[2, 0x3D, 0xD8, 0x00, 0xDE]);
+    check::<{ num::BE | len::U16 }, _>(&Utf16BE::from("€"), &[0, 1, 0x20, 0xAC]);
+
+    let err = Utf16LE::from_bytes::<LE>(&[1, 0x00, 0xD8]).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidChar(0xD800)));
+    let err = Utf16BE::decode_from_reader::<LE>(&mut &[2, 0xDC, 0x00, 0, b'a'][..]).unwrap_err();
+    assert!(matches!(err.kind(), ErrorKind::InvalidChar(0xDC00)));
+    let err = Utf16LE::from_bytes::<LE>(&[2, b'H', 0, b'i']).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ErrorKind::InsufficientBytes { missing: Some(1) }
+    ));
+}
+
+#[test]
+fn test_latin1() {
+    const LE: u16 = num::LE;
+    check::<LE, _>(&Latin1::from("café"), &[4, b'c', b'a', b'f', 0xE9]);
+    check::<{ num::LE | len::U8 }, _>(&Latin1::from("ÿ"), &[1, 0xFF]);
+    assert_eq!(
+        Latin1::from_bytes::<LE>(&[2, 0x80, 0xA9]).unwrap().0,
+        "\u{80}©"
+    );
+
+    let err = Latin1::from("€").encode::<LE>(&mut Vec::new()).unwrap_err();
+    assert!(err.get_ref().unwrap().is::<InvalidChar>());
+}
+
+#[test]
+fn test_lossy_utf8() {
+    const LE: u16 = num::LE;
+    let text = LossyUtf8::from("hello");
+    check::<LE, _>(&text, &String::from("hello").to_bytes::<LE>());
+
+    let bytes = [4, b'a', 0xF0, 0x9F, b'b'];
+    assert!(String::from_bytes::<LE>(&bytes).is_err());
+    assert_eq!(LossyUtf8::from_bytes::<LE>(&bytes).unwrap().0, "a\u{FFFD}b");
+    assert_eq!(
+        LossyUtf8::decode_from_reader::<LE>(&mut &bytes[..])
+            .unwrap()
+            .0,
+        "a\u{FFFD}b"
+    );
+}
+
+#[test]
+fn test_text_fields() {
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    struct Record {
+        name: NulTerminated,
+        title: Utf16LE,
+        #[databuf(len = U8)]
+        city: Latin1,
+    }
+    let record = Record {
+        name: "ab".into(),
+        title: "c".into(),
+        city: "é".into(),
+    };
+    check::<{ num::LE }, _>(&record, &[b'a', b'b', 0, 1, b'c', 0, 1, 0xE9]);
+}
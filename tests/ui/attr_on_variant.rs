@@ -0,0 +1,10 @@
+use databuf::Encode;
+
+#[derive(Encode)]
+enum Shape {
+    Dot,
+    #[databuf(num = BE)]
+    Circle(u32),
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use databuf::Encode;
+
+#[derive(Encode)]
+struct Flags {
+    #[databuf(bits = 0)]
+    empty: u8,
+    #[databuf(bits = 65)]
+    wide: u128,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use databuf::Encode;
+
+#[derive(Encode)]
+#[databuf(bit_order = LsbFirst, bit_order = MsbFirst)]
+struct Point {
+    x: u32,
+    y: u32,
+}
+
+fn main() {}
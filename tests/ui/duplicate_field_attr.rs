@@ -0,0 +1,11 @@
+use databuf::Encode;
+
+#[derive(Encode)]
+struct Point {
+    x: u32,
+    #[databuf(num = BE)]
+    #[databuf(num = LE)]
+    y: u32,
+}
+
+fn main() {}
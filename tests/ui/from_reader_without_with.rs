@@ -0,0 +1,10 @@
+use databuf::Decode;
+
+#[derive(Decode)]
+struct Point {
+    x: u32,
+    #[databuf(from_reader)]
+    y: u32,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use databuf::Encode;
+
+#[derive(Encode)]
+struct Point {
+    x: u32,
+    #[databuf(skip, num = BE)]
+    y: u32,
+    #[databuf(default = "u32::default", with = "crate")]
+    z: u32,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+use databuf::Encode;
+
+#[derive(Encode)]
+#[databuf(transparent)]
+struct Nibble(#[databuf(bits = 8)] u8);
+
+fn main() {}
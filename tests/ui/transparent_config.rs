@@ -0,0 +1,7 @@
+use databuf::Encode;
+
+#[derive(Encode)]
+#[databuf(transparent, config = databuf::config::num::LE)]
+struct Meters(u32);
+
+fn main() {}
@@ -0,0 +1,10 @@
+use databuf::Encode;
+
+#[derive(Encode)]
+#[databuf(compact)]
+struct Point {
+    x: u32,
+    y: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use databuf::Encode;
+
+#[derive(Encode)]
+struct Point {
+    x: u32,
+    #[databuf(num = BE, endian = LE)]
+    y: u32,
+}
+
+fn main() {}
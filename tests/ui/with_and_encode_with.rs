@@ -0,0 +1,10 @@
+use databuf::Encode;
+
+#[derive(Encode)]
+struct Point {
+    x: u32,
+    #[databuf(with = "crate", encode_with = "crate::encode")]
+    y: u32,
+}
+
+fn main() {}